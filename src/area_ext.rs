@@ -0,0 +1,84 @@
+// External includes.
+
+// Standard includes.
+use std::vec::IntoIter;
+
+// Internal includes.
+use crate::geometry::*;
+
+/// Helper methods for iterating over the positions of an [`Area`](geometry/struct.Area.html); implemented for `Area`.
+///
+/// Will iterate over the edge and the inside of an area 4 tiles wide by 3 tiles high.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let area = Area::new(Position::new(2, 1), Size::new(4, 3));
+///
+/// let perimeter = area.perimeter_positions().collect::<Vec<Position>>();
+/// assert!(perimeter == vec![
+///     Position::new(2, 1), Position::new(3, 1), Position::new(4, 1), Position::new(5, 1),
+///     Position::new(5, 2),
+///     Position::new(5, 3), Position::new(4, 3), Position::new(3, 3), Position::new(2, 3),
+///     Position::new(2, 2),
+/// ]);
+///
+/// let interior = area.interior_positions().collect::<Vec<Position>>();
+/// assert!(interior == vec![Position::new(3, 2), Position::new(4, 2)]);
+///
+/// // An area 1 tile high is all perimeter.
+/// let line = Area::new(Position::zero(), Size::new(3, 1));
+/// assert!(line.perimeter_positions().count() == 3);
+/// assert!(line.interior_positions().count() == 0);
+/// assert!(Area::new(Position::zero(), Size::zero()).perimeter_positions().count() == 0);
+/// ```
+pub trait AreaExt {
+    /// Returns the positions on the edge of the area, each once, clockwise from the top-left corner.
+    fn perimeter_positions(&self) -> IntoIter<Position>;
+
+    /// Returns the positions inside the area which are not on its edge, in row-major order.
+    fn interior_positions(&self) -> IntoIter<Position>;
+}
+
+impl AreaExt for Area {
+    fn perimeter_positions(&self) -> IntoIter<Position> {
+        let mut output = Vec::new();
+        let (width, height) = (self.size().width() as i32, self.size().height() as i32);
+        if width == 0 || height == 0 {
+            return output.into_iter();
+        }
+
+        let (left, top) = (self.position().x(), self.position().y());
+        let (right, bottom) = (left + width - 1, top + height - 1);
+        for x in left..=right {
+            output.push(Position::new(x, top));
+        }
+        for y in (top + 1)..=bottom {
+            output.push(Position::new(right, y));
+        }
+        if bottom > top {
+            for x in (left..right).rev() {
+                output.push(Position::new(x, bottom));
+            }
+        }
+        if right > left {
+            for y in ((top + 1)..bottom).rev() {
+                output.push(Position::new(left, y));
+            }
+        }
+
+        output.into_iter()
+    }
+
+    fn interior_positions(&self) -> IntoIter<Position> {
+        let (left, top) = (self.position().x(), self.position().y());
+        let (width, height) = (self.size().width() as i32, self.size().height() as i32);
+        let mut output = Vec::new();
+        for y in (top + 1)..(top + height - 1) {
+            for x in (left + 1)..(left + width - 1) {
+                output.push(Position::new(x, y));
+            }
+        }
+
+        output.into_iter()
+    }
+}
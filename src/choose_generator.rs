@@ -0,0 +1,83 @@
+// External includes.
+use rand::distributions::{Distribution, WeightedIndex};
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::dun_gen_rng::dun_gen_rng;
+
+/// Used to execute one of a weighted set of dungeon generators, chosen randomly each time it is run.
+///
+/// Each generator is chosen with a likelihood proportional to its weight; a generator with a weight of 0 is never chosen. Wrapped in a [`TraversePortalsGenerator`](struct.TraversePortalsGenerator.html), this gives each map at the end of a portal a randomly-chosen kind of room.
+///
+/// The following code will run a generator weighted 3 about three times as often as a generator weighted 1.
+///```
+/// # use dungen_minion::*;
+/// use std::cell::Cell;
+///
+/// struct CountingGenerator<'a> {
+///     count: &'a Cell<usize>,
+/// }
+///
+/// impl<'a> DoesDunGen for CountingGenerator<'a> {
+///     fn dun_gen_map(&self, _map_id: MapId) {
+///         self.count.set(self.count.get() + 1);
+///     }
+/// }
+///
+/// let rare = Cell::new(0);
+/// let common = Cell::new(0);
+/// let never = Cell::new(0);
+/// let rare_generator = CountingGenerator { count: &rare };
+/// let common_generator = CountingGenerator { count: &common };
+/// let never_generator = CountingGenerator { count: &never };
+/// let choices: [(u32, &dyn DoesDunGen); 3] = [
+///     (1, &rare_generator),
+///     (3, &common_generator),
+///     (0, &never_generator),
+/// ];
+/// let choose = ChooseGenerator::new(&choices);
+/// let map_id = SparseMap::new();
+/// for _ in 0..10_000 {
+///     choose.dun_gen_map(map_id);
+/// }
+///
+/// assert!(rare.get() + common.get() == 10_000);
+/// assert!(rare.get() > 2_000 && rare.get() < 3_000);
+/// assert!(common.get() > 7_000 && common.get() < 8_000);
+/// assert!(never.get() == 0);
+///```
+pub struct ChooseGenerator<'a> {
+    choices: &'a [(u32, &'a dyn DoesDunGen)],
+    weighted_index: WeightedIndex<u32>,
+}
+
+impl<'a> ChooseGenerator<'a> {
+    /// Creates a new weighted choice of dungeon generators.
+    ///
+    /// Panics if there are no choices, or if every weight is 0.
+    pub fn new(choices: &'a [(u32, &'a dyn DoesDunGen)]) -> Self {
+        let weighted_index = WeightedIndex::new(choices.iter().map(|(weight, _)| *weight))
+            .expect("ChooseGenerator requires at least one non-zero weight.");
+
+        Self {
+            choices,
+            weighted_index,
+        }
+    }
+
+    fn choose(&self) -> &'a dyn DoesDunGen {
+        self.choices[self.weighted_index.sample(&mut dun_gen_rng())].1
+    }
+}
+
+impl<'a> DoesDunGen for ChooseGenerator<'a> {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        self.choose().dun_gen(target);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        self.choose().dun_gen_map(map_id);
+    }
+}
@@ -0,0 +1,159 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use crate::geometry::*;
+use crate::shape_ext::edge_containment;
+
+/// A circular placed shape, for generating round rooms with generators which take a [`ProvidesPlacedShape`](geometry/trait.ProvidesPlacedShape.html), such as [`FillTilesGenerator`](struct.FillTilesGenerator.html) and [`WalledRoomGenerator`](struct.WalledRoomGenerator.html).
+///
+/// A `CircleShape` covers every position whose distance from the centre rounds to no more than the radius; its [`Area`](geometry/struct.Area.html) is the square, `radius * 2 + 1` tiles wide, around the centre. Positions inside the circle with a neighbour outside of it are its edge, and so are walled by `WalledRoomGenerator`.
+///
+/// Will create a round room with a radius of 4 tiles, in a map 9 tiles wide by 9 tiles high.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let circle = CircleShape::new(Position::new(4, 4), 4);
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(9, 9), TileType::Void))
+///     .gen_with(FillTilesGenerator::new(circle, TileType::Floor))
+///     .gen_with(WalledRoomGenerator::new(circle))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// for corner in &[
+///     Position::new(0, 0),
+///     Position::new(8, 0),
+///     Position::new(0, 8),
+///     Position::new(8, 8),
+/// ] {
+///     assert!(map.tile_type_at_local(*corner).unwrap_or(TileType::Void) == TileType::Void);
+/// }
+/// assert!(map.tile_type_at_local(Position::new(4, 4)) == Some(TileType::Floor));
+/// assert!(map.tile_type_at_local(Position::new(4, 0)) == Some(TileType::Wall));
+/// assert!(map.tile_type_at_local(Position::new(8, 4)) == Some(TileType::Wall));
+/// assert!(map.tile_type_at_local(Position::new(4, 1)) == Some(TileType::Floor));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct CircleShape {
+    area: Area,
+    radius: u32,
+}
+
+impl CircleShape {
+    /// Creates a new `CircleShape` with the given centre and radius.
+    pub fn new(center: Position, radius: u32) -> Self {
+        let offset = radius as i32;
+        let diameter = radius * 2 + 1;
+        Self {
+            area: Area::new(
+                center - Position::new(offset, offset),
+                Size::new(diameter, diameter),
+            ),
+            radius,
+        }
+    }
+}
+
+impl ContainsLocalPosition for CircleShape {
+    fn contains_local_position(&self, position: Position) -> Containment {
+        edge_containment(self, position)
+    }
+}
+
+impl ContainsPosition for CircleShape {}
+
+impl HasArea for CircleShape {
+    fn area(&self) -> &Area {
+        &self.area
+    }
+
+    fn area_mut(&mut self) -> &mut Area {
+        &mut self.area
+    }
+}
+
+impl HasHeight for CircleShape {
+    fn height(&self) -> Length {
+        self.size().height()
+    }
+
+    fn height_mut(&mut self) -> &mut Length {
+        self.size_mut().height_mut()
+    }
+}
+
+impl HasPosition for CircleShape {
+    fn position(&self) -> &Position {
+        self.area.position()
+    }
+
+    fn position_mut(&mut self) -> &mut Position {
+        self.area.position_mut()
+    }
+}
+
+impl HasSize for CircleShape {
+    fn size(&self) -> &Size {
+        self.area.size()
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        self.area.size_mut()
+    }
+}
+
+impl HasWidth for CircleShape {
+    fn width(&self) -> Length {
+        self.size().width()
+    }
+
+    fn width_mut(&mut self) -> &mut Length {
+        self.size_mut().width_mut()
+    }
+}
+
+impl IntersectsLocalPosition for CircleShape {
+    fn intersects_local_position(&self, position: Position) -> bool {
+        let radius = self.radius as i64;
+        let x = position.x() as i64 - radius;
+        let y = position.y() as i64 - radius;
+
+        // (radius + 0.5) squared, rounded down; rounds the edge of the circle out to whole tiles.
+        (x * x) + (y * y) <= (radius * radius) + radius
+    }
+}
+
+impl IntersectsPosition for CircleShape {}
+
+impl Placed for CircleShape {}
+
+impl PlacedObject for CircleShape {}
+
+impl ProvidesArea for CircleShape {
+    fn provide_area(&self) -> Area {
+        self.area.provide_area()
+    }
+}
+
+impl ProvidesPlacedShape for CircleShape {
+    fn provide_placed_shape(&self) -> Box<dyn PlacedShape> {
+        Box::new(*self)
+    }
+}
+
+impl ProvidesSize for CircleShape {
+    fn provide_size(&self) -> Size {
+        self.area.provide_size()
+    }
+}
+
+impl Shape for CircleShape {
+    fn box_shape_clone(&self) -> Box<dyn Shape> {
+        Box::new(*self)
+    }
+}
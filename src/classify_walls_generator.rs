@@ -0,0 +1,132 @@
+// External includes.
+
+// Standard includes.
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// The kind of a wall tile, as classified by a [`ClassifyWallsGenerator`](struct.ClassifyWallsGenerator.html).
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum WallKind {
+    /// A wall on the outside of the map; on the edge of the map, or next to a tile which is `TileType::Void` or missing.
+    Perimeter,
+    /// A wall inside the map, which is joined to at least one other wall.
+    Interior,
+    /// A wall inside the map, which is not joined to any other wall.
+    Pillar,
+}
+
+/// A generator for classifying the walls of a map, for rendering perimeter walls and interior walls differently.
+///
+/// The `ClassifyWallsGenerator` does not change the map. Instead, it gives every [`TileType`](enum.TileType.html)::Wall tile a [`WallKind`](enum.WallKind.html), by looking at its orthogonal neighbours; a wall which touches the edge of the map or `TileType::Void` is `WallKind::Perimeter`, a wall with no neighbouring walls is `WallKind::Pillar`, and any other wall is `WallKind::Interior`.
+///
+/// The classification is stored per [`MapId`](type.MapId.html), and can be retrieved after generation through [`ClassifyWallsGenerator::classification()`](#method.classification).
+///
+/// Will create a walled room 10 tiles wide by 7 tiles high, with a pillar and a short wall inside it, and classify its walls.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let classify_walls_generator = ClassifyWallsGenerator::new();
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(10, 7)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(2, 2), Size::new(1, 1)),
+///         TileType::Wall))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(5, 2), Size::new(1, 2)),
+///         TileType::Wall))
+///     .build();
+/// classify_walls_generator.dun_gen_map(map_id);
+///
+/// let classification = classify_walls_generator.classification(map_id);
+/// assert!(classification.len() == (10 * 2) + ((7 * 2) - 4) + 3);
+/// for x in 0..10 {
+///     assert!(classification[&Position::new(x, 0)] == WallKind::Perimeter);
+///     assert!(classification[&Position::new(x, 6)] == WallKind::Perimeter);
+/// }
+/// for y in 0..7 {
+///     assert!(classification[&Position::new(0, y)] == WallKind::Perimeter);
+///     assert!(classification[&Position::new(9, y)] == WallKind::Perimeter);
+/// }
+/// assert!(classification[&Position::new(2, 2)] == WallKind::Pillar);
+/// assert!(classification[&Position::new(5, 2)] == WallKind::Interior);
+/// assert!(classification[&Position::new(5, 3)] == WallKind::Interior);
+/// ```
+pub struct ClassifyWallsGenerator {
+    classifications: RwLock<HashMap<MapId, HashMap<Position, WallKind>>>,
+}
+
+impl ClassifyWallsGenerator {
+    /// Creates a new generator for classifying the walls of a map.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            classifications: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the [`WallKind`](enum.WallKind.html) of every wall tile on the map with the given `MapId`, keyed by local position. Returns an empty `HashMap` if the map has not been classified.
+    pub fn classification(&self, map_id: MapId) -> HashMap<Position, WallKind> {
+        self.classifications
+            .read()
+            .unwrap()
+            .get(&map_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl DoesDunGen for ClassifyWallsGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &maps[map_id].read();
+
+        let mut classification = HashMap::new();
+        map.visit_tiles(|position, tile_type| {
+            if tile_type != TileType::Wall {
+                return;
+            }
+
+            let neighbours = [
+                Position::NORTH,
+                Position::EAST,
+                Position::SOUTH,
+                Position::WEST,
+            ]
+            .iter()
+            .map(|direction| {
+                let neighbour = position + *direction;
+                if map.is_local_position_valid(neighbour) {
+                    map.tile_type_at_local_or_void(neighbour)
+                } else {
+                    TileType::Void
+                }
+            })
+            .collect::<Vec<_>>();
+
+            let wall_kind = if neighbours.contains(&TileType::Void) {
+                WallKind::Perimeter
+            } else if !neighbours.contains(&TileType::Wall) {
+                WallKind::Pillar
+            } else {
+                WallKind::Interior
+            };
+            classification.insert(position, wall_kind);
+        });
+
+        self.classifications
+            .write()
+            .unwrap()
+            .insert(map_id, classification);
+    }
+}
@@ -0,0 +1,128 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::default_tile_map::DEFAULT_TILES;
+use crate::geometry::*;
+use crate::map_ext::{OUT_OF_BOUNDS, TILE_ATTRS};
+use crate::portal_ext::PORTAL_LABELS;
+
+/// Copies the map with the given `MapId` into a new [`SparseMap`](struct.SparseMap.html), and returns the `MapId` of the copy.
+///
+/// If the map was created with [`SparseMap::with_default_tile()`](trait.SparseMapExt.html#tymethod.with_default_tile), so is the copy, with the same default tile.
+///
+/// The copy has the same [`Area`](geometry/struct.Area.html), tiles, tile attributes, bounds mask, [`Portal`](struct.Portal.html)s and their labels, and [`SubMap`](struct.SubMap.html)s as the original. The maps at the end of the portals, and the sub-maps, are not copied; the copy's portals and sub-maps refer to the same `MapId`s as the original's. Changes to the copy do not affect the original, so the copy can be used as a template to generate variations from.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(2, Box::new(|| SparseMap::new())))
+///     .build();
+///
+/// let clone_map_id = clone_map(map_id);
+/// assert!(clone_map_id != map_id);
+/// DunGen::new(clone_map_id)
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(2, 2), Size::new(2, 2)),
+///         TileType::Wall));
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// let cloned_map = maps[clone_map_id].read();
+///
+/// assert!(cloned_map.area() == map.area());
+/// assert!(map.count_tiles(TileType::Floor) == 6 * 4);
+/// assert!(cloned_map.count_tiles(TileType::Floor) == (6 * 4) - 4);
+/// assert!(map.tile_type_at_local(Position::new(2, 2)) == Some(TileType::Floor));
+/// assert!(cloned_map.tile_type_at_local(Position::new(2, 2)) == Some(TileType::Wall));
+///
+/// assert!(cloned_map.portal_count() == 2);
+/// for portal in map.portals() {
+///     let clone_portal = cloned_map.portal_at(*portal.local_position()).unwrap();
+///     assert!(clone_portal.target() == portal.target());
+///     assert!(clone_portal.portal_to_map_facing() == portal.portal_to_map_facing());
+/// }
+/// ```
+///
+/// Will clone a map created with a default tile; the copy fills its unset tiles with the same tile.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id = SparseMap::with_default_tile(TileType::Wall);
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     map.resize(Size::new(5, 5));
+///     map.tile_type_at_local_set(Position::new(2, 2), TileType::Floor);
+/// }
+///
+/// let clone_map_id = clone_map(map_id);
+///
+/// let maps = MAPS.read();
+/// let mut cloned_map = maps[clone_map_id].write();
+/// assert!(cloned_map.tile_type_at_local(Position::new(2, 2)) == Some(TileType::Floor));
+/// assert!(cloned_map.tile_type_at_local(Position::new(2, 3)) == Some(TileType::Wall));
+/// assert!(cloned_map.count_tiles(TileType::Wall) == (5 * 5) - 1);
+///
+/// cloned_map.resize(Size::new(6, 6));
+/// assert!(cloned_map.tile_type_at_local(Position::new(5, 5)) == Some(TileType::Wall));
+/// ```
+pub fn clone_map(map_id: MapId) -> MapId {
+    let default_tile = DEFAULT_TILES.read().unwrap().get(&map_id).copied();
+    let clone_map_id = match default_tile {
+        Some(default_tile) => SparseMap::with_default_tile(default_tile),
+        None => SparseMap::new(),
+    };
+    {
+        let maps = &MAPS.read();
+        let map = &maps[map_id].read();
+        let clone_map = &mut maps[clone_map_id].write();
+
+        for y in 0..map.size().height() {
+            for x in 0..map.size().width() {
+                let position = Position::new(x as i32, y as i32);
+                // Preferring any tile over no tile also copies tiles, such as `TileType::Void`,
+                // which the usual tile priority hides.
+                let tile_type = map.tile_type_at_local_sort_by(position, &|left, right| {
+                    left.is_some().cmp(&right.is_some())
+                });
+                if let Some(tile_type) = tile_type {
+                    clone_map.tile_type_at_local_set(position, tile_type);
+                }
+            }
+        }
+        *clone_map.area_mut() = *map.area();
+
+        for portal in map.portals() {
+            clone_map.add_portal(
+                *portal.local_position(),
+                *portal.portal_to_map_facing(),
+                *portal.portal_to_map_position(),
+                portal.target(),
+            );
+        }
+        for sub_map in map.sub_maps() {
+            clone_map.add_sub_map(*sub_map.local_position(), sub_map.value());
+        }
+    }
+
+    let mut portal_labels = PORTAL_LABELS.write().unwrap();
+    if let Some(labels) = portal_labels.get(&map_id).cloned() {
+        portal_labels.insert(clone_map_id, labels);
+    }
+    let mut tile_attrs = TILE_ATTRS.write().unwrap();
+    if let Some(attrs) = tile_attrs.get(&map_id).cloned() {
+        tile_attrs.insert(clone_map_id, attrs);
+    }
+    let mut out_of_bounds = OUT_OF_BOUNDS.write().unwrap();
+    if let Some(positions) = out_of_bounds.get(&map_id).cloned() {
+        out_of_bounds.insert(clone_map_id, positions);
+    }
+
+    clone_map_id
+}
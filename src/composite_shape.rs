@@ -0,0 +1,230 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use crate::geometry::*;
+use crate::shape_ext::edge_containment;
+
+/// The way a [`CompositeShape`](struct.CompositeShape.html) combines its two shapes.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ShapeOp {
+    /// Covers the positions covered by either shape.
+    Union,
+    /// Covers the positions covered by both shapes.
+    Intersection,
+    /// Covers the positions covered by the first shape, but not by the second.
+    Difference,
+}
+
+/// A placed shape made by combining two other placed shapes with a [`ShapeOp`](enum.ShapeOp.html), for building complex room footprints to use with generators which take a [`ProvidesPlacedShape`](geometry/trait.ProvidesPlacedShape.html), such as [`FillTilesGenerator`](struct.FillTilesGenerator.html).
+///
+/// The two shapes are provided once, when the `CompositeShape` is created. Its [`Area`](geometry/struct.Area.html) is the smallest area enclosing both shapes for `ShapeOp::Union`, and the area of the first shape otherwise. Moving the composite shape, with `position_mut()` or `area_mut()`, moves both shapes with it. Positions inside the composite shape with a neighbour outside of it are its edge.
+///
+/// Will create a floor 12 tiles wide by 8 tiles high, with a circular bite taken out of it.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let rectangle = Area::new(Position::zero(), Size::new(12, 8));
+/// let circle = CircleShape::new(Position::new(8, 4), 2);
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(
+///         CompositeShape::new(rectangle, circle, ShapeOp::Difference),
+///         TileType::Floor))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// for y in 0..8 {
+///     for x in 0..12 {
+///         let position = Position::new(x, y);
+///         let in_bite = circle.intersects_position(position);
+///         assert!((map.tile_type_at_local(position) == Some(TileType::Floor)) == !in_bite);
+///     }
+/// }
+/// assert!(map.tile_type_at_local(Position::new(8, 4)) != Some(TileType::Floor));
+/// assert!(map.tile_type_at_local(Position::new(2, 4)) == Some(TileType::Floor));
+/// ```
+///
+/// Will move a union of two rectangles 3 tiles right and 2 tiles down; both rectangles move with it.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let mut shape = CompositeShape::new(
+///     Area::new(Position::zero(), Size::new(4, 2)),
+///     Area::new(Position::new(6, 0), Size::new(2, 2)),
+///     ShapeOp::Union);
+/// *shape.position_mut() = Position::new(3, 2);
+///
+/// assert!(*shape.area() == Area::new(Position::new(3, 2), Size::new(8, 2)));
+/// assert!(shape.intersects_position(Position::new(3, 2)));
+/// assert!(shape.intersects_position(Position::new(6, 3)));
+/// assert!(!shape.intersects_position(Position::new(7, 2)));
+/// assert!(shape.intersects_position(Position::new(10, 3)));
+/// assert!(!shape.intersects_position(Position::new(0, 0)));
+/// assert!(!shape.intersects_position(Position::new(6, 0)));
+///
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(shape, TileType::Floor))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// assert!(map.count_tiles(TileType::Floor) == (4 * 2) + (2 * 2));
+/// assert!(map.tile_type_at_local(Position::new(3, 2)) == Some(TileType::Floor));
+/// assert!(map.tile_type_at_local(Position::new(10, 3)) == Some(TileType::Floor));
+/// assert!(map.tile_type_at_local(Position::new(0, 0)) != Some(TileType::Floor));
+/// ```
+#[derive(Clone)]
+pub struct CompositeShape {
+    area: Area,
+    // The position of the area when the shapes were provided; the shapes are tested relative to
+    // it, so that moving the composite shape moves both of them.
+    origin: Position,
+    a: Box<dyn PlacedShape>,
+    b: Box<dyn PlacedShape>,
+    op: ShapeOp,
+}
+
+impl CompositeShape {
+    /// Creates a new `CompositeShape` from two placed shapes, and the way to combine them.
+    pub fn new<TProvidesPlacedShapeA, TProvidesPlacedShapeB>(
+        a: TProvidesPlacedShapeA,
+        b: TProvidesPlacedShapeB,
+        op: ShapeOp,
+    ) -> Self
+    where
+        TProvidesPlacedShapeA: ProvidesPlacedShape,
+        TProvidesPlacedShapeB: ProvidesPlacedShape,
+    {
+        let a = a.provide_placed_shape();
+        let b = b.provide_placed_shape();
+        let area = match op {
+            ShapeOp::Union => {
+                let left = a.left().min(b.left());
+                let top = a.top().min(b.top());
+                let right = a.right().max(b.right());
+                let bottom = a.bottom().max(b.bottom());
+                Area::new(
+                    Position::new(left, top),
+                    Size::new((right - left + 1) as u32, (bottom - top + 1) as u32),
+                )
+            }
+            ShapeOp::Intersection | ShapeOp::Difference => *a.area(),
+        };
+
+        Self {
+            area,
+            origin: *area.position(),
+            a,
+            b,
+            op,
+        }
+    }
+}
+
+impl ContainsLocalPosition for CompositeShape {
+    fn contains_local_position(&self, position: Position) -> Containment {
+        edge_containment(self, position)
+    }
+}
+
+impl ContainsPosition for CompositeShape {}
+
+impl HasArea for CompositeShape {
+    fn area(&self) -> &Area {
+        &self.area
+    }
+
+    fn area_mut(&mut self) -> &mut Area {
+        &mut self.area
+    }
+}
+
+impl HasHeight for CompositeShape {
+    fn height(&self) -> Length {
+        self.size().height()
+    }
+
+    fn height_mut(&mut self) -> &mut Length {
+        self.size_mut().height_mut()
+    }
+}
+
+impl HasPosition for CompositeShape {
+    fn position(&self) -> &Position {
+        self.area.position()
+    }
+
+    fn position_mut(&mut self) -> &mut Position {
+        self.area.position_mut()
+    }
+}
+
+impl HasSize for CompositeShape {
+    fn size(&self) -> &Size {
+        self.area.size()
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        self.area.size_mut()
+    }
+}
+
+impl HasWidth for CompositeShape {
+    fn width(&self) -> Length {
+        self.size().width()
+    }
+
+    fn width_mut(&mut self) -> &mut Length {
+        self.size_mut().width_mut()
+    }
+}
+
+impl IntersectsLocalPosition for CompositeShape {
+    fn intersects_local_position(&self, position: Position) -> bool {
+        // The two shapes are placed independently, so test them at the matching position where
+        // they were placed.
+        let position = position + self.origin;
+        let in_a = self.a.intersects_position(position);
+        let in_b = self.b.intersects_position(position);
+        match self.op {
+            ShapeOp::Union => in_a || in_b,
+            ShapeOp::Intersection => in_a && in_b,
+            ShapeOp::Difference => in_a && !in_b,
+        }
+    }
+}
+
+impl IntersectsPosition for CompositeShape {}
+
+impl Placed for CompositeShape {}
+
+impl PlacedObject for CompositeShape {}
+
+impl ProvidesArea for CompositeShape {
+    fn provide_area(&self) -> Area {
+        self.area.provide_area()
+    }
+}
+
+impl ProvidesPlacedShape for CompositeShape {
+    fn provide_placed_shape(&self) -> Box<dyn PlacedShape> {
+        Box::new(self.clone())
+    }
+}
+
+impl ProvidesSize for CompositeShape {
+    fn provide_size(&self) -> Size {
+        self.area.provide_size()
+    }
+}
+
+impl Shape for CompositeShape {
+    fn box_shape_clone(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+}
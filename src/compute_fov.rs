@@ -0,0 +1,183 @@
+// External includes.
+
+// Standard includes.
+use std::collections::HashSet;
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A slope, as a fraction with a positive denominator.
+#[derive(Copy, Clone)]
+struct Slope {
+    numerator: i32,
+    denominator: i32,
+}
+
+/// A row of tiles at a given depth from the origin, between a start and end slope.
+#[derive(Copy, Clone)]
+struct Row {
+    depth: i32,
+    start_slope: Slope,
+    end_slope: Slope,
+}
+
+impl Row {
+    fn min_col(&self) -> i32 {
+        // Rounds `depth * start_slope` to the nearest column, rounding ties up.
+        let Slope {
+            numerator,
+            denominator,
+        } = self.start_slope;
+        (2 * self.depth * numerator + denominator).div_euclid(2 * denominator)
+    }
+
+    fn max_col(&self) -> i32 {
+        // Rounds `depth * end_slope` to the nearest column, rounding ties down.
+        let Slope {
+            numerator,
+            denominator,
+        } = self.end_slope;
+        -(denominator - 2 * self.depth * numerator).div_euclid(2 * denominator)
+    }
+
+    fn is_symmetric(&self, col: i32) -> bool {
+        col * self.start_slope.denominator >= self.depth * self.start_slope.numerator
+            && col * self.end_slope.denominator <= self.depth * self.end_slope.numerator
+    }
+
+    fn next(&self) -> Row {
+        Row {
+            depth: self.depth + 1,
+            ..*self
+        }
+    }
+}
+
+/// Returns the local [`Position`](geometry/struct.Position.html)s visible from the given origin, on the map with the given `MapId`, out to the given radius.
+///
+/// Uses symmetric shadowcasting; if one floor tile can see another, the other can see it back. Tiles which are [opaque](trait.TileTypeExt.html#tymethod.is_opaque) block sight, but are visible themselves. Positions with no tile, including those outside of the map, block sight, and are never visible. A position is within the radius if its straight-line distance from the origin is no greater than the radius. The origin is always visible.
+///
+/// Will create an open room 11 tiles wide by 11 tiles high, with a single wall two tiles north of the middle.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(11, 11)))
+///     .build();
+/// let origin = Position::new(5, 5);
+///
+/// // In an open room, everything within the radius is visible.
+/// let visible = compute_fov(map_id, origin, 3);
+/// let mut within_radius = 0;
+/// for y in 0..11 {
+///     for x in 0..11 {
+///         let (dx, dy) = (x - origin.x(), y - origin.y());
+///         if dx * dx + dy * dy <= 3 * 3 {
+///             assert!(visible.contains(&Position::new(x, y)));
+///             within_radius += 1;
+///         }
+///     }
+/// }
+/// assert!(visible.len() == within_radius);
+///
+/// // A wall casts a shadow behind it.
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     map.tile_type_at_local_set(Position::new(5, 3), TileType::Wall);
+/// }
+/// let visible = compute_fov(map_id, origin, 10);
+/// assert!(visible.contains(&Position::new(5, 4)));
+/// assert!(visible.contains(&Position::new(5, 3)));
+/// assert!(!visible.contains(&Position::new(5, 2)));
+/// assert!(!visible.contains(&Position::new(5, 1)));
+/// assert!(visible.contains(&Position::new(0, 0)));
+///
+/// // Sight is symmetric between floor tiles.
+/// let visible_floors = {
+///     let maps = MAPS.read();
+///     let map = maps[map_id].read();
+///     visible
+///         .iter()
+///         .filter(|position| map.tile_type_at_local(**position) == Some(TileType::Floor))
+///         .copied()
+///         .collect::<Vec<_>>()
+/// };
+/// for position in visible_floors {
+///     assert!(compute_fov(map_id, position, 10).contains(&origin));
+/// }
+/// ```
+pub fn compute_fov(map_id: MapId, origin: Position, radius: u32) -> HashSet<Position> {
+    let maps = &MAPS.read();
+    let map = &maps[map_id].read();
+    let radius = radius as i32;
+    let mut visible = HashSet::new();
+    visible.insert(origin);
+
+    let is_opaque = |position: Position| match map.tile_type_at_local(position) {
+        Some(tile_type) => tile_type.is_opaque(),
+        None => true,
+    };
+
+    // North, east, south, and west; each maps (depth, col) to an offset from the origin.
+    let quadrants: [fn(i32, i32) -> Position; 4] = [
+        |depth, col| Position::new(col, -depth),
+        |depth, col| Position::new(depth, col),
+        |depth, col| Position::new(col, depth),
+        |depth, col| Position::new(-depth, col),
+    ];
+    for transform in quadrants.iter() {
+        let mut rows = vec![Row {
+            depth: 1,
+            start_slope: Slope {
+                numerator: -1,
+                denominator: 1,
+            },
+            end_slope: Slope {
+                numerator: 1,
+                denominator: 1,
+            },
+        }];
+
+        while let Some(mut row) = rows.pop() {
+            if row.depth > radius {
+                continue;
+            }
+
+            let mut previous_is_opaque = None;
+            for col in row.min_col()..=row.max_col() {
+                let position = origin + transform(row.depth, col);
+                let opaque = is_opaque(position);
+                let within_radius = row.depth * row.depth + col * col <= radius * radius;
+                if within_radius
+                    && map.tile_type_at_local(position).is_some()
+                    && (opaque || row.is_symmetric(col))
+                {
+                    visible.insert(position);
+                }
+
+                let slope = Slope {
+                    numerator: 2 * col - 1,
+                    denominator: 2 * row.depth,
+                };
+                if previous_is_opaque == Some(true) && !opaque {
+                    row.start_slope = slope;
+                }
+                if previous_is_opaque == Some(false) && opaque {
+                    let mut next_row = row.next();
+                    next_row.end_slope = slope;
+                    rows.push(next_row);
+                }
+                previous_is_opaque = Some(opaque);
+            }
+
+            if previous_is_opaque == Some(false) {
+                rows.push(row.next());
+            }
+        }
+    }
+
+    visible
+}
@@ -0,0 +1,44 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use crate::geometry::*;
+
+/// Which neighbouring tiles count as connected to a tile, when finding groups of connected tiles.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Connectivity {
+    /// Only the 4 orthogonally adjacent tiles are connected.
+    Four,
+    /// The 4 orthogonally adjacent tiles, and the 4 diagonally adjacent tiles, are connected; tiles which only touch at a corner are connected.
+    Eight,
+}
+
+impl Connectivity {
+    /// Returns the offsets of the connected neighbours of a tile; orthogonal neighbours first, in the order north, east, south, west, followed by any diagonal neighbours.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// assert!(Connectivity::Four.neighbor_offsets().len() == 4);
+    /// assert!(Connectivity::Eight.neighbor_offsets().len() == 8);
+    /// assert!(Connectivity::Eight.neighbor_offsets().contains(&Position::new(1, 1)));
+    /// ```
+    pub fn neighbor_offsets(&self) -> Vec<Position> {
+        let mut offsets = vec![
+            Position::NORTH,
+            Position::EAST,
+            Position::SOUTH,
+            Position::WEST,
+        ];
+        if *self == Connectivity::Eight {
+            offsets.extend_from_slice(&[
+                Position::NORTH + Position::EAST,
+                Position::SOUTH + Position::EAST,
+                Position::SOUTH + Position::WEST,
+                Position::NORTH + Position::WEST,
+            ]);
+        }
+
+        offsets
+    }
+}
@@ -0,0 +1,144 @@
+// External includes.
+use rand::distributions::Distribution;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Standard includes.
+
+// Internal includes.
+use crate::dun_gen_rng::dun_gen_rng;
+use crate::geometry::*;
+
+/// Provides random sizes from a [`SizeRange`](geometry/struct.SizeRange.html), limited to those with an area and an aspect ratio within the given bounds.
+///
+/// Sizes are drawn from the range until one fits; its area, width times height, must be within [min_area, max_area], and its aspect ratio, the longer side divided by the shorter side, must be no greater than max_aspect. Panics on creation if min_area is greater than max_area, if max_aspect is less than 1, or if no fitting size is drawn from the range after many tries, as the constraints then likely cannot be met by the range.
+///
+/// Draws many sizes, none of which are very small, very large, or very narrow.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let constrained_size_range = ConstrainedSizeRange::new(
+///     SizeRange::new(Size::new(2, 2), Size::new(20, 20)),
+///     16,
+///     150,
+///     2.0,
+/// );
+///
+/// for _ in 0..1_000 {
+///     let size = constrained_size_range.provide_size();
+///     let (width, height) = (size.width(), size.height());
+///     assert!(width * height >= 16 && width * height <= 150);
+///     assert!(width.max(height) as f64 / width.min(height) as f64 <= 2.0);
+/// }
+///
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(constrained_size_range))
+///     .build();
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// let area = map.size().width() * map.size().height();
+/// assert!(area >= 16 && area <= 150);
+/// ```
+///
+/// Constraints which cannot be met panic when the `ConstrainedSizeRange` is created, rather than when it is first drawn from.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// use std::panic::catch_unwind;
+///
+/// let range = SizeRange::new(Size::new(2, 2), Size::new(20, 20));
+/// assert!(catch_unwind(|| ConstrainedSizeRange::new(range, 150, 16, 2.0)).is_err());
+/// assert!(catch_unwind(|| ConstrainedSizeRange::new(range, 16, 150, 0.5)).is_err());
+/// // No size in the range has an area of at least 500.
+/// assert!(catch_unwind(|| ConstrainedSizeRange::new(range, 500, 1_000, 2.0)).is_err());
+/// // No size in the range is this square.
+/// let narrow_range = SizeRange::new(Size::new(2, 10), Size::new(4, 20));
+/// assert!(catch_unwind(|| ConstrainedSizeRange::new(narrow_range, 1, 1_000, 1.5)).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ConstrainedSizeRange {
+    range: SizeRange,
+    min_area: u32,
+    max_area: u32,
+    max_aspect: f64,
+}
+
+impl ConstrainedSizeRange {
+    const MAX_TRIES: usize = 10_000;
+
+    /// Creates a new `ConstrainedSizeRange`, drawing from the given range, with the given bounds on area and aspect ratio.
+    pub fn new(range: SizeRange, min_area: u32, max_area: u32, max_aspect: f64) -> Self {
+        assert!(
+            min_area <= max_area,
+            "ConstrainedSizeRange requires a min_area no greater than its max_area."
+        );
+        assert!(
+            max_aspect >= 1.0,
+            "ConstrainedSizeRange requires a max_aspect of at least 1, as the aspect ratio is the longer side divided by the shorter side."
+        );
+
+        let constrained_size_range = Self {
+            range,
+            min_area,
+            max_area,
+            max_aspect,
+        };
+        // A fixed seed, so that the same constraints are always accepted or always rejected.
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(
+            constrained_size_range.try_sample(&mut rng).is_some(),
+            "ConstrainedSizeRange could not find a size in its range which meets its area and aspect ratio constraints."
+        );
+
+        constrained_size_range
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Size> {
+        for _ in 0..Self::MAX_TRIES {
+            let size = self.range.sample(rng);
+            if self.fits(size) {
+                return Some(size);
+            }
+        }
+
+        None
+    }
+
+    fn fits(&self, size: Size) -> bool {
+        let (width, height) = (size.width(), size.height());
+        let area = width * height;
+        let aspect = width.max(height) as f64 / width.min(height).max(1) as f64;
+
+        area >= self.min_area && area <= self.max_area && aspect <= self.max_aspect
+    }
+}
+
+impl Distribution<Size> for ConstrainedSizeRange {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Size {
+        match self.try_sample(rng) {
+            Some(size) => size,
+            None => {
+                panic!("ConstrainedSizeRange could not find a size which meets its constraints.")
+            }
+        }
+    }
+}
+
+impl ProvidesArea for ConstrainedSizeRange {
+    fn provide_area(&self) -> Area {
+        Area::from(self.provide_size())
+    }
+}
+
+impl ProvidesPlacedShape for ConstrainedSizeRange {
+    fn provide_placed_shape(&self) -> Box<dyn PlacedShape> {
+        Box::new(self.provide_area())
+    }
+}
+
+impl ProvidesSize for ConstrainedSizeRange {
+    fn provide_size(&self) -> Size {
+        self.sample(&mut dun_gen_rng())
+    }
+}
@@ -0,0 +1,43 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use crate::geometry::*;
+
+/// Provides a count from a closure, so that a count worked out at generation time can be given to anything which takes a [`ProvidesCount`](geometry/trait.ProvidesCount.html), without defining a new type.
+///
+/// The closure is called each time a count is asked for.
+///
+/// Will create a map with a number of portals decided at runtime.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let portals_wanted = std::env::args().count() + 2;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 8)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(
+///         CountFn(|| portals_wanted),
+///         Box::new(|| SparseMap::new()),
+///     ))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// assert!(map.portal_count() == portals_wanted);
+/// ```
+#[derive(Copy, Clone)]
+pub struct CountFn<TFunc>(pub TFunc)
+where
+    TFunc: Fn() -> Count;
+
+impl<TFunc> ProvidesCount for CountFn<TFunc>
+where
+    TFunc: Fn() -> Count,
+{
+    fn provide_count(&self) -> Count {
+        (self.0)()
+    }
+}
@@ -0,0 +1,71 @@
+// External includes.
+
+// Standard includes.
+use std::cell::Cell;
+
+// Internal includes.
+use crate::geometry::*;
+
+thread_local! {
+    /// The facing of the portal whose target map is currently being generated.
+    static CURRENT_PORTAL_FACING: Cell<Option<CardinalDirection>> = const { Cell::new(None) };
+}
+
+/// Returns the facing of the [`Portal`](struct.Portal.html) whose target map is currently being generated by a [`TraversePortalsGenerator`](struct.TraversePortalsGenerator.html) or a [`DunGenStream`](struct.DunGenStream.html), or `None` if no portal is being traversed on this thread.
+///
+/// Lets generators, and the shapes and sizes they are given, adapt to the portal that leads to the map; see [`DirectionalSizeProvider`](struct.DirectionalSizeProvider.html).
+/// ```
+/// # use dungen_minion::*;
+/// assert!(current_portal_facing() == None);
+/// ```
+///
+/// The facing is cleared once the portal has been traversed, even if a generator panics while traversing it.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// use std::panic::{catch_unwind, AssertUnwindSafe};
+///
+/// struct PanickingGenerator;
+///
+/// impl DoesDunGen for PanickingGenerator {
+///     fn dun_gen_map(&self, _map_id: MapId) {
+///         assert!(current_portal_facing().is_some());
+///         panic!("Generation failed.");
+///     }
+/// }
+///
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(1, Box::new(|| SparseMap::new())))
+///     .build();
+///
+/// let result = catch_unwind(AssertUnwindSafe(|| {
+///     DunGen::new(map_id).gen_with(TraversePortalsGenerator::new(PanickingGenerator));
+/// }));
+/// assert!(result.is_err());
+/// assert!(current_portal_facing() == None);
+/// ```
+pub fn current_portal_facing() -> Option<CardinalDirection> {
+    CURRENT_PORTAL_FACING.with(|facing| facing.get())
+}
+
+/// Calls the given function with the current portal facing set to the given facing.
+pub(crate) fn with_portal_facing<TFunc>(facing: CardinalDirection, func: TFunc)
+where
+    TFunc: FnOnce(),
+{
+    // Restores the previous facing when dropped, so that it is restored even if `func` panics.
+    struct RestoreFacing(Option<CardinalDirection>);
+
+    impl Drop for RestoreFacing {
+        fn drop(&mut self) {
+            CURRENT_PORTAL_FACING.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore_facing =
+        RestoreFacing(CURRENT_PORTAL_FACING.with(|current| current.replace(Some(facing))));
+    func();
+}
@@ -0,0 +1,176 @@
+// External includes.
+use rand::seq::SliceRandom;
+
+// Standard includes.
+use std::collections::HashSet;
+
+// Internal includes.
+use super::*;
+use crate::dun_gen_rng::dun_gen_rng;
+use crate::geometry::*;
+
+/// A generator for carving short dead-end stubs off of existing corridors.
+///
+/// The `DeadEndGenerator` is called with an explicit count, or an instance of [`ProvidesCount`](geometry/trait.ProvidesCount.html), of stubs to carve, and a `ProvidesCount` for the length of each stub. Each stub is a straight line of [`TileType`](enum.TileType.html)::Floor carved through `TileType::Wall`, branching from a floor tile which already has at least two floor neighbours; the stub never touches any other floor or portal tiles, so it always ends in a dead end. Stubs do not reach the edge of the map.
+///
+/// If there is no room left for a stub of the chosen length, that stub is skipped.
+///
+/// Will create a walled room with a `Size` of 30 tiles wide by 20 tiles high, fill it in, carve two crossing corridors, and then carve 5 dead ends off of them.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let count_dead_ends = |map_id: MapId| {
+///     let maps = MAPS.read();
+///     let map = maps[map_id].read();
+///     let mut dead_end_count = 0;
+///     map.visit_tiles(|position, tile_type| {
+///         let floor_neighbour_count = [Position::NORTH, Position::EAST, Position::SOUTH, Position::WEST]
+///             .iter()
+///             .filter(|direction| {
+///                 map.tile_type_at_local(position + **direction) == Some(TileType::Floor)
+///             })
+///             .count();
+///         if tile_type == TileType::Floor && floor_neighbour_count == 1 {
+///             dead_end_count += 1;
+///         }
+///     });
+///     dead_end_count
+/// };
+///
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(30, 20)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(1, 1), Size::new(28, 18)),
+///         TileType::Wall))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(2, 10), Size::new(26, 1)),
+///         TileType::Floor))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(15, 2), Size::new(1, 16)),
+///         TileType::Floor))
+///     .build();
+///
+/// // The ends of the two corridors.
+/// assert!(count_dead_ends(map_id) == 4);
+///
+/// DeadEndGenerator::new(5, CountRange::new(2, 4)).dun_gen_map(map_id);
+/// assert!(count_dead_ends(map_id) == 4 + 5);
+/// ```
+pub struct DeadEndGenerator<TProvidesCount, TProvidesLength>
+where
+    TProvidesCount: ProvidesCount + Sized,
+    TProvidesLength: ProvidesCount + Sized,
+{
+    provides_count: TProvidesCount,
+    provides_length: TProvidesLength,
+}
+
+impl<TProvidesCount, TProvidesLength> DeadEndGenerator<TProvidesCount, TProvidesLength>
+where
+    TProvidesCount: ProvidesCount + Sized,
+    TProvidesLength: ProvidesCount + Sized,
+{
+    /// Creates a new generator for carving dead-end stubs off of existing corridors.
+    pub fn new(provides_count: TProvidesCount, provides_length: TProvidesLength) -> Self {
+        Self {
+            provides_count,
+            provides_length,
+        }
+    }
+}
+
+impl<TProvidesCount, TProvidesLength> DoesDunGen
+    for DeadEndGenerator<TProvidesCount, TProvidesLength>
+where
+    TProvidesCount: ProvidesCount + Sized,
+    TProvidesLength: ProvidesCount + Sized,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let directions = [
+            Position::NORTH,
+            Position::EAST,
+            Position::SOUTH,
+            Position::WEST,
+        ];
+        let width = map.size().width() as i32;
+        let height = map.size().height() as i32;
+        let mut rng = dun_gen_rng();
+        // Stub tiles are never used as branching points, so that stubs stay dead ends.
+        let mut stub_tiles = HashSet::new();
+
+        let count = self.provides_count.provide_count();
+        for _ in 0..count {
+            let length = self.provides_length.provide_count().max(1) as i32;
+            let is_open = |position: Position| {
+                matches!(
+                    map.tile_type_at_local(position),
+                    Some(tile_type) if tile_type.is_walkable()
+                )
+            };
+            let is_interior = |position: Position| {
+                position.x() > 0
+                    && position.y() > 0
+                    && position.x() < width - 1
+                    && position.y() < height - 1
+            };
+
+            let mut candidates = Vec::new();
+            for y in 0..height {
+                for x in 0..width {
+                    let origin = Position::new(x, y);
+                    if map.tile_type_at_local(origin) != Some(TileType::Floor)
+                        || stub_tiles.contains(&origin)
+                        || directions
+                            .iter()
+                            .filter(|direction| is_open(origin + **direction))
+                            .count()
+                            < 2
+                    {
+                        continue;
+                    }
+
+                    for direction in directions.iter() {
+                        let side = Position::new(direction.y(), direction.x());
+                        let fits = (1..=length).all(|step| {
+                            let position =
+                                origin + Position::new(direction.x() * step, direction.y() * step);
+                            is_interior(position)
+                                && map.tile_type_at_local(position) == Some(TileType::Wall)
+                                && !is_open(position + side)
+                                && !is_open(position - side)
+                        }) && !is_open(
+                            origin
+                                + Position::new(
+                                    direction.x() * (length + 1),
+                                    direction.y() * (length + 1),
+                                ),
+                        );
+                        if fits {
+                            candidates.push((origin, *direction));
+                        }
+                    }
+                }
+            }
+
+            let (origin, direction) = match candidates.choose(&mut rng) {
+                Some(candidate) => *candidate,
+                None => continue,
+            };
+            for step in 1..=length {
+                let position = origin + Position::new(direction.x() * step, direction.y() * step);
+                map.tile_type_at_local_set(position, TileType::Floor);
+                stub_tiles.insert(position);
+            }
+        }
+    }
+}
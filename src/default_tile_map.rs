@@ -0,0 +1,227 @@
+// External includes.
+use lazy_static::lazy_static;
+
+// Standard includes.
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+lazy_static! {
+    /// The default tiles of the maps wrapped in a `DefaultTileMap`, keyed by `MapId`.
+    pub(crate) static ref DEFAULT_TILES: RwLock<HashMap<MapId, TileType>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Wraps a [`Map`](trait.Map.html), returning a default [`TileType`](enum.TileType.html) for local positions within the map's size which have no tile.
+///
+/// Installed by [`SparseMapExt::with_default_tile()`](trait.SparseMapExt.html#tymethod.with_default_tile); every other method forwards to the wrapped map.
+#[derive(Clone)]
+pub(crate) struct DefaultTileMap {
+    map: Box<dyn Map>,
+    default_tile: TileType,
+}
+
+impl DefaultTileMap {
+    pub(crate) fn new(map: Box<dyn Map>, default_tile: TileType) -> Self {
+        Self { map, default_tile }
+    }
+
+    fn is_unset(&self, pos: Position) -> bool {
+        let size = self.map.size();
+        // Preferring any tile over no tile finds tiles, such as `TileType::Void`, which the usual
+        // tile priority hides.
+        pos.x() >= 0
+            && pos.y() >= 0
+            && (pos.x() as u32) < size.width()
+            && (pos.y() as u32) < size.height()
+            && self
+                .map
+                .tile_type_at_local_sort_by(pos, &|left, right| {
+                    left.is_some().cmp(&right.is_some())
+                })
+                .is_none()
+    }
+}
+
+impl ContainsLocalPosition for DefaultTileMap {
+    fn contains_local_position(&self, position: Position) -> Containment {
+        self.map.contains_local_position(position)
+    }
+}
+
+impl ContainsPosition for DefaultTileMap {}
+
+impl HasArea for DefaultTileMap {
+    fn area(&self) -> &Area {
+        self.map.area()
+    }
+
+    fn area_mut(&mut self) -> &mut Area {
+        self.map.area_mut()
+    }
+}
+
+impl HasHeight for DefaultTileMap {
+    fn height(&self) -> Length {
+        self.map.height()
+    }
+
+    fn height_mut(&mut self) -> &mut Length {
+        self.map.height_mut()
+    }
+}
+
+impl HasPosition for DefaultTileMap {
+    fn position(&self) -> &Position {
+        self.map.position()
+    }
+
+    fn position_mut(&mut self) -> &mut Position {
+        self.map.position_mut()
+    }
+}
+
+impl HasSize for DefaultTileMap {
+    fn size(&self) -> &Size {
+        self.map.size()
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        self.map.size_mut()
+    }
+}
+
+impl HasWidth for DefaultTileMap {
+    fn width(&self) -> Length {
+        self.map.width()
+    }
+
+    fn width_mut(&mut self) -> &mut Length {
+        self.map.width_mut()
+    }
+}
+
+impl IntersectsLocalPosition for DefaultTileMap {
+    fn intersects_local_position(&self, position: Position) -> bool {
+        self.map.intersects_local_position(position)
+    }
+}
+
+impl IntersectsPosition for DefaultTileMap {}
+
+impl Map for DefaultTileMap {
+    fn box_clone(&self) -> Box<dyn Map> {
+        Box::new((*self).clone())
+    }
+
+    fn is_local_position_valid(&self, position: Position) -> bool {
+        self.map.is_local_position_valid(position)
+    }
+
+    fn map_id(&self) -> MapId {
+        self.map.map_id()
+    }
+
+    fn rotate(&mut self, rotation: CardinalRotation) {
+        self.map.rotate(rotation)
+    }
+
+    fn tile_type_at_local(&self, pos: Position) -> Option<TileType> {
+        match self.map.tile_type_at_local(pos) {
+            None if self.is_unset(pos) => Some(self.default_tile),
+            output => output,
+        }
+    }
+
+    fn tile_type_at_local_mut(&mut self, pos: Position) -> Option<&mut TileType> {
+        self.map.tile_type_at_local_mut(pos)
+    }
+
+    fn tile_type_at_local_set(&mut self, pos: Position, tile_type: TileType) -> Option<TileType> {
+        self.map.tile_type_at_local_set(pos, tile_type)
+    }
+
+    fn tile_type_at_local_sort_by(
+        &self,
+        pos: Position,
+        sort_best: &dyn Fn(&Option<TileType>, &Option<TileType>) -> std::cmp::Ordering,
+    ) -> Option<TileType> {
+        self.map.tile_type_at_local_sort_by(pos, sort_best)
+    }
+}
+
+impl Placed for DefaultTileMap {}
+
+impl PlacedObject for DefaultTileMap {}
+
+impl PortalCollection for DefaultTileMap {
+    fn add_portal(
+        &mut self,
+        local_position: Position,
+        portal_to_map_facing: CardinalDirection,
+        portal_to_map_position: Position,
+        target: MapId,
+    ) {
+        self.map.add_portal(
+            local_position,
+            portal_to_map_facing,
+            portal_to_map_position,
+            target,
+        )
+    }
+
+    fn get_portal_at(&self, index: usize) -> Option<&Portal> {
+        self.map.get_portal_at(index)
+    }
+
+    fn get_portal_at_mut(&mut self, index: usize) -> Option<&mut Portal> {
+        self.map.get_portal_at_mut(index)
+    }
+
+    fn portal_count(&self) -> usize {
+        self.map.portal_count()
+    }
+
+    fn portals(&self) -> Portals<'_> {
+        self.map.portals()
+    }
+
+    fn portals_mut(&mut self) -> PortalsMut<'_> {
+        self.map.portals_mut()
+    }
+}
+
+impl Shape for DefaultTileMap {
+    fn box_shape_clone(&self) -> Box<dyn Shape> {
+        Box::new((*self).clone())
+    }
+}
+
+impl SubMapCollection for DefaultTileMap {
+    fn add_sub_map(&mut self, local_position: Position, target: MapId) {
+        self.map.add_sub_map(local_position, target)
+    }
+
+    fn get_sub_map_at(&self, index: usize) -> Option<&SubMap> {
+        self.map.get_sub_map_at(index)
+    }
+
+    fn get_sub_map_at_mut(&mut self, index: usize) -> Option<&mut SubMap> {
+        self.map.get_sub_map_at_mut(index)
+    }
+
+    fn sub_map_count(&self) -> usize {
+        self.map.sub_map_count()
+    }
+
+    fn sub_maps(&self) -> SubMaps<'_> {
+        self.map.sub_maps()
+    }
+
+    fn sub_maps_mut(&mut self) -> SubMapsMut<'_> {
+        self.map.sub_maps_mut()
+    }
+}
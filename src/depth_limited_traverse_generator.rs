@@ -0,0 +1,85 @@
+// External includes.
+
+// Standard includes.
+use std::collections::{HashSet, VecDeque};
+
+// Internal includes.
+use super::*;
+
+/// Used to execute a dungeon generator on every map within a number of portal hops of a map.
+///
+/// The `DepthLimitedTraverseGenerator` applies the inner generator to the given map, and then to each map reachable through [`Portal`](struct.Portal.html)s, breadth-first, up to `max_depth` hops away. Each map's portals are followed after the inner generator has been applied to it, so portals added by the inner generator are traversed as well. Each map is only generated once, even if the portals form cycles.
+///
+/// The following code will link 5 maps in a chain, and then generate rooms on the first map and the two maps after it.
+///```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let chain = (0..5).map(|_| SparseMap::new()).collect::<Vec<_>>();
+/// {
+///     let maps = MAPS.read();
+///     for link in chain.windows(2) {
+///         maps[link[0]]
+///             .write()
+///             .add_portal(Position::zero(), CardinalDirection::East, Position::zero(), link[1]);
+///     }
+/// }
+///
+/// DunGen::new(chain[0])
+///     .gen_with(DepthLimitedTraverseGenerator::new(2, EmptyRoomGenerator::new(Size::new(4, 4))));
+///
+/// let maps = MAPS.read();
+/// for (depth, map_id) in chain.iter().enumerate() {
+///     let generated = *maps[*map_id].read().size() == Size::new(4, 4);
+///     assert!(generated == (depth <= 2));
+/// }
+///```
+pub struct DepthLimitedTraverseGenerator<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    max_depth: usize,
+    dun_gen: TDunGen,
+}
+
+impl<TDunGen> DepthLimitedTraverseGenerator<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    /// Creates a dungeon generator that traverses portals up to `max_depth` hops from the starting map.
+    pub fn new(max_depth: usize, dun_gen: TDunGen) -> Self {
+        Self { max_depth, dun_gen }
+    }
+}
+
+impl<TDunGen> DoesDunGen for DepthLimitedTraverseGenerator<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let mut visited = HashSet::new();
+        let mut on_maps = VecDeque::new();
+        visited.insert(map_id);
+        on_maps.push_back((map_id, 0));
+
+        while let Some((map_id, depth)) = on_maps.pop_front() {
+            self.dun_gen.dun_gen_map(map_id);
+            if depth == self.max_depth {
+                continue;
+            }
+
+            let maps = &MAPS.read();
+            let map = &maps[map_id].read();
+            for portal in map.portals() {
+                let target_map_id = portal.target();
+                if visited.insert(target_map_id) {
+                    on_maps.push_back((target_map_id, depth + 1));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// External includes.
+
+// Standard includes.
+use std::collections::HashMap;
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// Provides random sizes from a [`SizeRange`](geometry/struct.SizeRange.html) chosen by the facing of the portal being traversed.
+///
+/// While a [`TraversePortalsGenerator`](struct.TraversePortalsGenerator.html) generates the map at the end of a [`Portal`](struct.Portal.html), the size is drawn from the range given for the portal's facing with [`DirectionalSizeProvider::with_direction()`](#method.with_direction). Otherwise, including outside of a traversal, the size is drawn from the default range.
+///
+/// Will create a map with portals on its left and right walls, and generate wide rooms at the end of them; the portals face east and west, into the map.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let wide = SizeRange::new(Size::new(8, 3), Size::new(12, 5));
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 8)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(
+///         EdgePortalsGenerator::new(4, Box::new(|| SparseMap::new()))
+///             .with_walls(&[CardinalDirection::East, CardinalDirection::West]))
+///     .gen_with(TraversePortalsGenerator::new(EmptyRoomGenerator::new(
+///         DirectionalSizeProvider::new(SizeRange::new(Size::new(3, 3), Size::new(4, 4)))
+///             .with_direction(CardinalDirection::East, wide)
+///             .with_direction(CardinalDirection::West, wide),
+///     )))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// assert!(map.portal_count() == 4);
+/// for portal in map.portals() {
+///     let target_map = maps[portal.target()].read();
+///     assert!(target_map.size().width() > target_map.size().height());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DirectionalSizeProvider {
+    default: SizeRange,
+    directions: HashMap<CardinalDirection, SizeRange>,
+}
+
+impl DirectionalSizeProvider {
+    /// Creates a new `DirectionalSizeProvider`, drawing from the given range for every facing.
+    pub fn new(default: SizeRange) -> Self {
+        Self {
+            default,
+            directions: HashMap::new(),
+        }
+    }
+
+    /// Sets the range to draw from for portals with the given facing.
+    pub fn with_direction(mut self, facing: CardinalDirection, size_range: SizeRange) -> Self {
+        self.directions.insert(facing, size_range);
+        self
+    }
+}
+
+impl ProvidesArea for DirectionalSizeProvider {
+    fn provide_area(&self) -> Area {
+        Area::from(self.provide_size())
+    }
+}
+
+impl ProvidesPlacedShape for DirectionalSizeProvider {
+    fn provide_placed_shape(&self) -> Box<dyn PlacedShape> {
+        Box::new(self.provide_area())
+    }
+}
+
+impl ProvidesSize for DirectionalSizeProvider {
+    fn provide_size(&self) -> Size {
+        current_portal_facing()
+            .and_then(|facing| self.directions.get(&facing))
+            .unwrap_or(&self.default)
+            .provide_size()
+    }
+}
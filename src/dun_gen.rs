@@ -1,13 +1,23 @@
 // External includes.
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rayon::prelude::*;
 
 // Standard includes.
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
 
 // Internal includes.
 use super::*;
+use crate::dun_gen_rng::with_seeded_rng;
+use crate::geometry::*;
+use crate::tile_sender_map::{TileSenderHandle, TileSenderMap};
 
 /// A new dungeon generator for generating dungeons based on a starting [`Map`](trait.Map.html).
 pub struct DunGen {
     map_id: MapId,
+    rng: Option<StdRng>,
+    tile_sender: Option<TileSenderHandle>,
 }
 
 impl DunGen {
@@ -23,7 +33,150 @@ impl DunGen {
     ///     .build();
     ///```
     pub fn new(map_id: MapId) -> Self {
-        Self { map_id }
+        Self {
+            map_id,
+            rng: None,
+            tile_sender: None,
+        }
+    }
+
+    /// Seeds the random numbers drawn by this crate's generators while this dungeon generator runs them, so that the same seed and the same chain of generators build the same dungeon.
+    ///
+    /// The seed is used by every generation method called after it. Sizes, counts, and positions drawn by the providers from `dungen_minion_geometry`, such as [`SizeRange`](geometry/struct.SizeRange.html) and [`CountRange`](geometry/struct.CountRange.html), are not seeded; use fixed sizes and counts with them for reproducible dungeons.
+    ///```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let build = |seed: u64| {
+    ///     DunGen::new(SparseMap::new())
+    ///         .with_seed(seed)
+    ///         .gen_with(EmptyRoomGenerator::new(Size::new(24, 16)))
+    ///         .gen_with(WalledRoomGenerator::new(Size::zero()))
+    ///         .gen_with(NoiseFloorGenerator::new(0.2, 0.5))
+    ///         .gen_with(EdgePortalsGenerator::new(4, Box::new(|| SparseMap::new())))
+    ///         .build()
+    /// };
+    /// let first_map_id = build(42);
+    /// let second_map_id = build(42);
+    ///
+    /// let maps = MAPS.read();
+    /// let first_map = maps[first_map_id].read();
+    /// let second_map = maps[second_map_id].read();
+    /// assert!(first_map.to_ascii_string() == second_map.to_ascii_string());
+    /// assert!(first_map.portal_count() == 4);
+    /// for (portal, other_portal) in first_map.portals().into_iter().zip(second_map.portals()) {
+    ///     assert!(portal.local_position() == other_portal.local_position());
+    ///     assert!(portal.portal_to_map_facing() == other_portal.portal_to_map_facing());
+    /// }
+    ///```
+    ///
+    /// A generator which panics neither loses the seed, nor leaves it in use by unseeded generation, if the panic is caught.
+    ///```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// use std::panic::{catch_unwind, AssertUnwindSafe};
+    ///
+    /// struct PanickingGenerator;
+    ///
+    /// impl DoesDunGen for PanickingGenerator {
+    ///     fn dun_gen_map(&self, _map_id: MapId) {
+    ///         panic!("Generation failed.");
+    ///     }
+    /// }
+    ///
+    /// let mut dun_gen = DunGen::new(SparseMap::new()).with_seed(7);
+    /// dun_gen.gen_with(EmptyRoomGenerator::new(Size::new(24, 16)));
+    /// let result = catch_unwind(AssertUnwindSafe(|| {
+    ///     dun_gen.gen_with(PanickingGenerator);
+    /// }));
+    /// assert!(result.is_err());
+    ///
+    /// // Unseeded generation is unaffected, and leaves the seeded sequence alone.
+    /// DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(24, 16)))
+    ///     .gen_with(NoiseFloorGenerator::new(0.2, 0.5));
+    /// let map_id = dun_gen.gen_with(NoiseFloorGenerator::new(0.2, 0.5)).build();
+    ///
+    /// let expected_map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .with_seed(7)
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(24, 16)))
+    ///     .gen_with(NoiseFloorGenerator::new(0.2, 0.5))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    /// let expected_map = maps[expected_map_id].read();
+    /// assert!(map.to_ascii_string() == expected_map.to_ascii_string());
+    ///```
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Some(StdRng::seed_from_u64(seed));
+        self
+    }
+
+    fn seeded<TFunc>(&mut self, func: TFunc)
+    where
+        TFunc: FnOnce(&mut Self),
+    {
+        // Hands the random number generator back to the dungeon generator when dropped, so that the
+        // seed is kept even if `func` panics.
+        struct KeepRng<'a> {
+            dun_gen: &'a mut DunGen,
+            rng: Option<StdRng>,
+        }
+
+        impl<'a> Drop for KeepRng<'a> {
+            fn drop(&mut self) {
+                self.dun_gen.rng = self.rng.take();
+            }
+        }
+
+        let rng = self.rng.take();
+        let mut keep_rng = KeepRng { dun_gen: self, rng };
+        let KeepRng { dun_gen, rng } = &mut keep_rng;
+        with_seeded_rng(rng, || func(dun_gen));
+    }
+
+    /// Generates the given number of independent dungeons in parallel, and returns the `MapId` of each one's primary map.
+    ///
+    /// Each dungeon starts from a new map from the factory, and has the pipeline applied to it. The pipeline must not reach across from one dungeon to another; for example, by adding portals to a shared map.
+    ///```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_ids = DunGen::build_many(
+    ///     500,
+    ///     || {
+    ///         DunGen::new(SparseMap::new())
+    ///             .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+    ///             .build()
+    ///     },
+    ///     &WalledRoomGenerator::new(Size::zero()),
+    /// );
+    ///
+    /// assert!(map_ids.len() == 500);
+    /// let maps = MAPS.read();
+    /// for map_id in &map_ids {
+    ///     let map = maps[*map_id].read();
+    ///     assert!(*map.size() == Size::new(8, 6));
+    ///     assert!(map.count_tiles(TileType::Floor) == 6 * 4);
+    /// }
+    ///```
+    pub fn build_many<TFactory, TDoesDunGen>(
+        count: usize,
+        factory: TFactory,
+        pipeline: &TDoesDunGen,
+    ) -> Vec<MapId>
+    where
+        TFactory: Fn() -> MapId + Sync,
+        TDoesDunGen: DoesDunGen + Sync,
+    {
+        (0..count)
+            .into_par_iter()
+            .map(|_| {
+                let map_id = factory();
+                pipeline.dun_gen_map(map_id);
+                map_id
+            })
+            .collect()
     }
 
     /// Returns the `MapId` of the generated [`Map`](trait.Map.html) implementation.
@@ -79,7 +232,219 @@ impl DunGen {
     where
         TDoesDunGen: DoesDunGen,
     {
-        with.dun_gen(self);
+        self.seeded(|dun_gen| with.dun_gen(dun_gen));
+
+        self
+    }
+
+    /// The `DunGen` will apply the provided `TDoesDunGen` to its primary map, only if the condition returns `true` for the primary map's `MapId`.
+    ///
+    /// An inline alternative to [`IfMapThenGenerator`](struct.IfMapThenGenerator.html). The following chains will wall a map only if it is larger than 8 tiles wide by 6 tiles high.
+    ///```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let is_large = |map_id: MapId| {
+    ///     let size = *MAPS.read()[map_id].read().size();
+    ///     size.width() > 8 && size.height() > 6
+    /// };
+    ///
+    /// let large_map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 8)))
+    ///     .gen_if(is_large, WalledRoomGenerator::new(Size::zero()))
+    ///     .build();
+    /// let small_map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(4, 3)))
+    ///     .gen_if(is_large, WalledRoomGenerator::new(Size::zero()))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// assert!(maps[large_map_id].read().count_tiles(TileType::Wall) == (12 * 2) + ((8 * 2) - 4));
+    /// assert!(maps[small_map_id].read().count_tiles(TileType::Wall) == 0);
+    ///```
+    pub fn gen_if<TCondition, TDoesDunGen>(
+        &mut self,
+        condition: TCondition,
+        with: TDoesDunGen,
+    ) -> &mut Self
+    where
+        TCondition: Fn(MapId) -> bool,
+        TDoesDunGen: DoesDunGen,
+    {
+        if condition(self.map_id) {
+            self.seeded(|dun_gen| with.dun_gen(dun_gen));
+        }
+
+        self
+    }
+
+    /// The `DunGen` will apply the provided `TDoesDunGen` to its primary map the given number of times.
+    ///
+    /// Useful for generators which refine a map a step at a time, such as smoothing passes. The following chain will apply a generator which counts its applications 3 times; applying it 0 times does nothing.
+    ///```
+    /// # use dungen_minion::*;
+    /// use std::cell::Cell;
+    ///
+    /// struct CountingGenerator<'a> {
+    ///     count: &'a Cell<usize>,
+    /// }
+    ///
+    /// impl<'a> DoesDunGen for CountingGenerator<'a> {
+    ///     fn dun_gen_map(&self, _map_id: MapId) {
+    ///         self.count.set(self.count.get() + 1);
+    ///     }
+    /// }
+    ///
+    /// let count = Cell::new(0);
+    /// DunGen::new(SparseMap::new())
+    ///     .gen_repeat(3, CountingGenerator { count: &count });
+    /// assert!(count.get() == 3);
+    ///
+    /// count.set(0);
+    /// DunGen::new(SparseMap::new())
+    ///     .gen_repeat(0, CountingGenerator { count: &count });
+    /// assert!(count.get() == 0);
+    ///```
+    pub fn gen_repeat<TDoesDunGen>(&mut self, times: usize, with: TDoesDunGen) -> &mut Self
+    where
+        TDoesDunGen: DoesDunGen,
+    {
+        self.seeded(|dun_gen| {
+            for _ in 0..times {
+                with.dun_gen(dun_gen);
+            }
+        });
+
+        self
+    }
+
+    /// The `DunGen` will apply the provided `TDoesDunGen` to every leaf map; that is, every map reachable from its primary map through portals which has no portals of its own.
+    ///
+    /// The leaf maps are found before any of them are generated, so portals added by the generator are not followed. If the primary map has no portals, it is the only leaf map.
+    ///
+    /// The following code will build a small tree of maps, and only generate the two maps at the ends of its branches.
+    ///```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id = SparseMap::new();
+    /// let branch_map_id = SparseMap::new();
+    /// let leaf_map_ids = [SparseMap::new(), SparseMap::new()];
+    /// {
+    ///     let maps = MAPS.read();
+    ///     let mut map = maps[map_id].write();
+    ///     map.add_portal(Position::new(0, 1), CardinalDirection::East, Position::zero(), branch_map_id);
+    ///     map.add_portal(Position::new(2, 1), CardinalDirection::West, Position::zero(), leaf_map_ids[0]);
+    ///     let mut branch_map = maps[branch_map_id].write();
+    ///     branch_map.add_portal(Position::new(1, 0), CardinalDirection::South, Position::zero(), leaf_map_ids[1]);
+    /// }
+    ///
+    /// DunGen::new(map_id)
+    ///     .gen_leaf_portals_with(EmptyRoomGenerator::new(Size::new(4, 3)));
+    ///
+    /// let maps = MAPS.read();
+    /// for leaf_map_id in &leaf_map_ids {
+    ///     assert!(maps[*leaf_map_id].read().count_tiles(TileType::Floor) == 4 * 3);
+    /// }
+    /// assert!(maps[map_id].read().count_tiles(TileType::Floor) == 0);
+    /// assert!(maps[branch_map_id].read().count_tiles(TileType::Floor) == 0);
+    ///```
+    pub fn gen_leaf_portals_with<TDoesDunGen>(&mut self, with: TDoesDunGen) -> &mut Self
+    where
+        TDoesDunGen: DoesDunGen,
+    {
+        let reachable_map_ids = reachable_maps(self.map_id);
+        let leaf_map_ids = {
+            let maps = &MAPS.read();
+            reachable_map_ids
+                .into_iter()
+                .filter(|map_id| maps[*map_id].read().portal_count() == 0)
+                .collect::<Vec<_>>()
+        };
+
+        self.seeded(|_dun_gen| {
+            for leaf_map_id in leaf_map_ids {
+                with.dun_gen_map(leaf_map_id);
+            }
+        });
+
+        self
+    }
+
+    /// Sends the local [`Position`](geometry/struct.Position.html) and [`TileType`](enum.TileType.html) of every tile subsequently written to the primary map over the provided `Sender`, so that generation can be watched as it happens; for example, by a visualizer on another thread.
+    ///
+    /// Only tiles written with `tile_type_at_local_set`, or by adding a `Portal`, are sent; maps reached through portals are not watched. If the receiver is dropped, generation carries on without sending. The map stays watched until [`DunGen::without_tile_sender()`](#method.without_tile_sender) is called.
+    ///```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// use std::sync::mpsc::channel;
+    ///
+    /// let (tile_sender, tile_receiver) = channel();
+    /// let mut dun_gen = DunGen::new(SparseMap::new());
+    /// dun_gen
+    ///     .with_tile_sender(tile_sender)
+    ///     .gen_with(FillTilesGenerator::new(Size::new(4, 3), TileType::Floor));
+    ///
+    /// let updates = tile_receiver.try_iter().collect::<Vec<_>>();
+    /// assert!(updates.len() == 4 * 3);
+    /// assert!(updates[0] == (Position::new(0, 0), TileType::Floor));
+    /// assert!(updates.iter().all(|(_, tile_type)| *tile_type == TileType::Floor));
+    ///
+    /// // Generation carries on once the receiver is gone.
+    /// drop(tile_receiver);
+    /// let map_id = dun_gen.gen_with(WalledRoomGenerator::new(Size::zero())).build();
+    /// assert!(MAPS.read()[map_id].read().tile_type_at_local(Position::zero()) == Some(TileType::Wall));
+    ///```
+    pub fn with_tile_sender(&mut self, tile_sender: Sender<(Position, TileType)>) -> &mut Self {
+        self.without_tile_sender();
+
+        let tile_sender: TileSenderHandle = Arc::new(Mutex::new(Some(tile_sender)));
+        let maps = &MAPS.read();
+        let map = &mut maps[self.map_id].write();
+        let tile_sender_map = TileSenderMap::new(map.box_clone(), tile_sender.clone());
+        **map = Box::new(tile_sender_map);
+        self.tile_sender = Some(tile_sender);
+
+        self
+    }
+
+    /// Stops sending the tiles written to the primary map over the `Sender` provided to [`DunGen::with_tile_sender()`](#method.with_tile_sender), and drops the `Sender`, so that the receiver sees the channel close. Does nothing if this dungeon generator has no tile sender.
+    ///```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// use std::sync::mpsc::channel;
+    ///
+    /// let (tile_sender, tile_receiver) = channel();
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .with_tile_sender(tile_sender)
+    ///     .gen_with(FillTilesGenerator::new(Size::new(4, 3), TileType::Floor))
+    ///     .without_tile_sender()
+    ///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+    ///     .build();
+    ///
+    /// // Only the floor was sent, and the channel is closed.
+    /// let updates = tile_receiver.iter().collect::<Vec<_>>();
+    /// assert!(updates.len() == 4 * 3);
+    /// assert!(updates.iter().all(|(_, tile_type)| *tile_type == TileType::Floor));
+    ///
+    /// // Later writes to the map are not sent either.
+    /// let maps = MAPS.read();
+    /// let mut map = maps[map_id].write();
+    /// assert!(map.tile_type_at_local(Position::zero()) == Some(TileType::Wall));
+    /// map.tile_type_at_local_set(Position::new(1, 1), TileType::Wall);
+    /// assert!(tile_receiver.try_recv().is_err());
+    ///```
+    pub fn without_tile_sender(&mut self) -> &mut Self {
+        if let Some(tile_sender) = self.tile_sender.take() {
+            if let Ok(mut tile_sender) = tile_sender.lock() {
+                tile_sender.take();
+            }
+            // A detached `TileSenderMap` clones to the map it wraps.
+            let maps = &MAPS.read();
+            let map = &mut maps[self.map_id].write();
+            **map = map.box_clone();
+        }
 
         self
     }
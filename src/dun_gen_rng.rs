@@ -0,0 +1,82 @@
+// External includes.
+use rand::rngs::StdRng;
+use rand::{thread_rng, RngCore};
+
+// Standard includes.
+use std::cell::RefCell;
+
+// Internal includes.
+
+thread_local! {
+    /// The random number generator of the seeded `DunGen` currently generating on this thread.
+    static SEEDED_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// The random number generator used by the generators in this crate.
+///
+/// Draws from the seeded generator installed by `with_seeded_rng()`, if there is one on this thread; otherwise, from `rand::thread_rng()`.
+pub(crate) struct DunGenRng {}
+
+impl RngCore for DunGenRng {
+    fn next_u32(&mut self) -> u32 {
+        SEEDED_RNG.with(|rng| match rng.borrow_mut().as_mut() {
+            Some(rng) => rng.next_u32(),
+            None => thread_rng().next_u32(),
+        })
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        SEEDED_RNG.with(|rng| match rng.borrow_mut().as_mut() {
+            Some(rng) => rng.next_u64(),
+            None => thread_rng().next_u64(),
+        })
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        SEEDED_RNG.with(|rng| match rng.borrow_mut().as_mut() {
+            Some(rng) => rng.fill_bytes(dest),
+            None => thread_rng().fill_bytes(dest),
+        })
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        SEEDED_RNG.with(|rng| match rng.borrow_mut().as_mut() {
+            Some(rng) => rng.try_fill_bytes(dest),
+            None => thread_rng().try_fill_bytes(dest),
+        })
+    }
+}
+
+/// Returns the random number generator to be used by generators; use instead of `rand::thread_rng()`, so that seeded dungeons are reproducible.
+pub(crate) fn dun_gen_rng() -> DunGenRng {
+    DunGenRng {}
+}
+
+/// Calls the given function with the given random number generator installed for this thread, if there is one; the generator is advanced by any random numbers the function draws.
+pub(crate) fn with_seeded_rng<TFunc>(rng: &mut Option<StdRng>, func: TFunc)
+where
+    TFunc: FnOnce(),
+{
+    if rng.is_none() {
+        func();
+        return;
+    }
+
+    // Hands the advanced generator back, and reinstalls the previous one, when dropped; so that
+    // both happen even if `func` panics.
+    struct RestoreRng<'a> {
+        rng: &'a mut Option<StdRng>,
+        previous: Option<StdRng>,
+    }
+
+    impl<'a> Drop for RestoreRng<'a> {
+        fn drop(&mut self) {
+            let previous = self.previous.take();
+            *self.rng = SEEDED_RNG.with(|seeded_rng| seeded_rng.replace(previous));
+        }
+    }
+
+    let previous = SEEDED_RNG.with(|seeded_rng| seeded_rng.replace(rng.take()));
+    let _restore_rng = RestoreRng { rng, previous };
+    func();
+}
@@ -0,0 +1,131 @@
+// External includes.
+
+// Standard includes.
+use std::collections::{HashSet, VecDeque};
+
+// Internal includes.
+use super::*;
+use crate::current_portal_facing::with_portal_facing;
+use crate::geometry::*;
+
+/// Generates the maps at the ends of [`Portal`](struct.Portal.html)s one at a time, on demand, for dungeons too large, or too open-ended, to generate up front.
+///
+/// The stream starts from an already generated root map. Each call to [`DunGenStream::next_room()`](#method.next_room) takes the next map at the end of a portal which has not been generated yet, in breadth-first order from the root, generates it with the inner generator, and returns its `MapId`; if the inner generator adds portals to the map, the maps at their ends are generated by later calls. Each map is only generated once, even if several portals lead to it. As with [`TraversePortalsGenerator`](struct.TraversePortalsGenerator.html), [`current_portal_facing()`](fn.current_portal_facing.html) returns the facing of the portal leading to the map while it is generated.
+///
+/// `DunGenStream` is also an `Iterator` over the `MapId`s of the generated maps.
+///
+/// Will create a room with two portals, and then pull 10 rooms from the stream, each of which adds two more portals.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// use std::cell::RefCell;
+/// use std::collections::HashMap;
+///
+/// struct CountingGenerator<'a> {
+///     counts: &'a RefCell<HashMap<MapId, usize>>,
+/// }
+///
+/// impl<'a> DoesDunGen for CountingGenerator<'a> {
+///     fn dun_gen_map(&self, map_id: MapId) {
+///         *self.counts.borrow_mut().entry(map_id).or_insert(0) += 1;
+///     }
+/// }
+///
+/// let root_map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(2, Box::new(|| SparseMap::new())))
+///     .build();
+///
+/// let counts = RefCell::new(HashMap::new());
+/// let room_generator = EmptyRoomGenerator::new(Size::new(8, 6));
+/// let wall_generator = WalledRoomGenerator::new(Size::zero());
+/// let portal_generator = EdgePortalsGenerator::new(2, Box::new(|| SparseMap::new()));
+/// let counting_generator = CountingGenerator { counts: &counts };
+/// let generators: [&dyn DoesDunGen; 4] =
+///     [&room_generator, &wall_generator, &portal_generator, &counting_generator];
+/// let mut stream = DunGenStream::new(root_map_id, SequentialGenerator::new(&generators));
+///
+/// let mut linked = vec![root_map_id];
+/// for _ in 0..10 {
+///     let map_id = stream.next_room().unwrap();
+///     {
+///         let maps = MAPS.read();
+///         // Each room is reached through a portal on the root, or on an earlier room.
+///         assert!(linked.iter().any(|linked_map_id| {
+///             maps[*linked_map_id]
+///                 .read()
+///                 .portals()
+///                 .into_iter()
+///                 .any(|portal| portal.target() == map_id)
+///         }));
+///         assert!(*maps[map_id].read().size() == Size::new(8, 6));
+///     }
+///     linked.push(map_id);
+/// }
+///
+/// let counts = counts.borrow();
+/// assert!(counts.len() == 10);
+/// assert!(counts.values().all(|count| *count == 1));
+/// assert!(!counts.contains_key(&root_map_id));
+/// ```
+pub struct DunGenStream<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    dun_gen: TDunGen,
+    unexplored: VecDeque<(MapId, CardinalDirection)>,
+    visited: HashSet<MapId>,
+}
+
+impl<TDunGen> DunGenStream<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    /// Creates a stream which generates the maps reachable through portals from the given root map, using the given generator.
+    pub fn new(root: MapId, dun_gen: TDunGen) -> Self {
+        let mut output = Self {
+            dun_gen,
+            unexplored: VecDeque::new(),
+            visited: HashSet::new(),
+        };
+        output.visited.insert(root);
+        output.add_portal_targets(root);
+
+        output
+    }
+
+    /// Generates the next map at the end of a portal, and returns its `MapId`; or returns `None` if every map reachable through portals has been generated.
+    pub fn next_room(&mut self) -> Option<MapId> {
+        let (map_id, portal_to_map_facing) = self.unexplored.pop_front()?;
+        let dun_gen = &self.dun_gen;
+        with_portal_facing(portal_to_map_facing, || dun_gen.dun_gen_map(map_id));
+        self.add_portal_targets(map_id);
+
+        Some(map_id)
+    }
+
+    fn add_portal_targets(&mut self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &maps[map_id].read();
+        for portal in map.portals() {
+            let target_map_id = portal.target();
+            if self.visited.insert(target_map_id) {
+                self.unexplored
+                    .push_back((target_map_id, *portal.portal_to_map_facing()));
+            }
+        }
+    }
+}
+
+impl<TDunGen> Iterator for DunGenStream<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    type Item = MapId;
+
+    fn next(&mut self) -> Option<MapId> {
+        self.next_room()
+    }
+}
@@ -0,0 +1,61 @@
+// External includes.
+
+// Standard includes.
+use std::fmt;
+
+// Internal includes.
+use super::*;
+
+/// Displays the map with the given [`MapId`](type.MapId.html) as ASCII art, followed by a legend and a summary of its [`Portal`](struct.Portal.html)s.
+///
+/// Each tile is drawn as its character from [`TileTypeExt::to_char()`](trait.TileTypeExt.html#tymethod.to_char); `#` for [`TileType`](enum.TileType.html)::Wall, `.` for `TileType::Floor`, `+` for `TileType::Portal`, and a space for `TileType::Void` or no tile. The portal summary gives the number of portals, and the direction each faces, in the order they were added.
+///
+/// Locks [`MAPS`](struct.MAPS.html) for reading while it is formatted, and so should not be formatted while `MAPS` is locked for writing.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(5, 4)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .build();
+/// let target_map_id = SparseMap::new();
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     map.add_portal(Position::new(0, 1), CardinalDirection::East, Position::zero(), target_map_id);
+/// }
+///
+/// let output = format!("{}", DungeonView(map_id));
+/// let lines = output.lines().collect::<Vec<_>>();
+/// assert!(lines == vec![
+///     "#####",
+///     "+...#",
+///     "#...#",
+///     "#####",
+///     "Legend: # wall, . floor, + portal",
+///     "Portals: 1 (East)",
+/// ]);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DungeonView(pub MapId);
+
+impl fmt::Display for DungeonView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let maps = &MAPS.read();
+        let map = &maps[self.0].read();
+
+        write!(f, "{}", map.to_ascii_string())?;
+        writeln!(f, "Legend: # wall, . floor, + portal")?;
+        write!(f, "Portals: {}", map.portal_count())?;
+        let facings = map
+            .portals()
+            .into_iter()
+            .map(|portal| portal.portal_to_map_facing().to_string())
+            .collect::<Vec<_>>();
+        if !facings.is_empty() {
+            write!(f, " ({})", facings.join(", "))?;
+        }
+        writeln!(f)
+    }
+}
@@ -1,10 +1,11 @@
 // External includes.
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 // Standard includes.
 
 // Internal includes.
 use super::*;
+use crate::dun_gen_rng::dun_gen_rng;
 use crate::geometry::*;
 
 /// A generator for adding one or more instances of [`Portal`](struct.Portal.html) to the edges of a map.
@@ -54,12 +55,73 @@ use crate::geometry::*;
 ///     assert!(portal_count >= 2 && portal_count <= 5);
 /// })
 /// ```
+///
+/// Each `Portal` faces into the map, as a [`CardinalDirection`](geometry/enum.CardinalDirection.html); a portal on the left wall faces `CardinalDirection::East`, on the right wall `CardinalDirection::West`, on the top wall `CardinalDirection::South`, and on the bottom wall `CardinalDirection::North`.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(10, Box::new(|| SparseMap::new())))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// for portal in map.portals() {
+///     let local_position = *portal.local_position();
+///     let facing = *portal.portal_to_map_facing();
+///     if local_position.x() == 0 {
+///         assert!(facing == CardinalDirection::East);
+///     } else if local_position.x() == 7 {
+///         assert!(facing == CardinalDirection::West);
+///     } else if local_position.y() == 0 {
+///         assert!(facing == CardinalDirection::South);
+///     } else {
+///         assert!(local_position.y() == 5);
+///         assert!(facing == CardinalDirection::North);
+///     }
+/// }
+/// ```
+///
+/// The portals can be restricted to some of the walls of the map with [`EdgePortalsGenerator::with_walls()`](#method.with_walls). Will create 3 portals on the bottom wall of a map, only, each on a different tile; and then fill all 6 tiles of the bottom wall with portals.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// use std::collections::HashSet;
+/// for &num_portals in [3, 6].iter() {
+///     for _ in 0..100 {
+///         let map_id =
+///             DunGen::new(SparseMap::new())
+///             .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///             .gen_with(WalledRoomGenerator::new(Size::zero()))
+///             .gen_with(
+///                 EdgePortalsGenerator::new(num_portals, Box::new(|| SparseMap::new()))
+///                     .with_walls(&[CardinalDirection::South]))
+///             .build();
+///
+///         let maps = MAPS.read();
+///         let map = maps[map_id].read();
+///         assert!(map.portal_count() == num_portals);
+///         assert!(map.count_tiles(TileType::Portal) == num_portals);
+///         let positions =
+///             map.portals().into_iter().map(|portal| *portal.local_position()).collect::<HashSet<_>>();
+///         assert!(positions.len() == num_portals);
+///         for portal in map.portals() {
+///             assert!(portal.local_position().y() == 5);
+///             assert!(*portal.portal_to_map_facing() == CardinalDirection::North);
+///         }
+///     }
+/// }
+/// ```
 pub struct EdgePortalsGenerator<TProvidesCount>
 where
     TProvidesCount: ProvidesCount + Sized,
 {
     provides_count: TProvidesCount,
     placed_map_box_func: Box<dyn Fn() -> MapId>,
+    walls: Option<Vec<CardinalDirection>>,
 }
 
 impl<TProvidesCount> EdgePortalsGenerator<TProvidesCount>
@@ -74,6 +136,20 @@ where
         Self {
             provides_count,
             placed_map_box_func,
+            walls: None,
+        }
+    }
+
+    /// Restricts the portals to the given walls of the map; for example, `CardinalDirection::South` for the bottom wall. By default, portals can be placed on any wall.
+    pub fn with_walls(mut self, walls: &[CardinalDirection]) -> Self {
+        self.walls = Some(walls.to_vec());
+        self
+    }
+
+    fn allows_wall(&self, wall: CardinalDirection) -> bool {
+        match &self.walls {
+            Some(walls) => walls.contains(&wall),
+            None => true,
         }
     }
 }
@@ -99,40 +175,47 @@ where
             }
 
             let mut edge_tiles = Vec::new();
-            for x in (map.left() + 1)..map.right() {
-                let position = Position::new(x, 0);
-                if map.contains_position(position) == Containment::Intersects {
-                    edge_tiles.push(position);
+            if self.allows_wall(CardinalDirection::North) {
+                for x in (map.left() + 1)..map.right() {
+                    let position = Position::new(x, 0);
+                    if map.contains_position(position) == Containment::Intersects {
+                        edge_tiles.push(position);
+                    }
                 }
             }
             for y in (map.top() + 1)..map.bottom() {
-                {
+                if self.allows_wall(CardinalDirection::West) {
                     let position = Position::new(map.left(), y);
                     if map.contains_position(position) == Containment::Intersects {
                         edge_tiles.push(position);
                     }
                 }
 
-                {
+                if self.allows_wall(CardinalDirection::East) {
                     let position = Position::new(map.right(), y);
                     if map.contains_position(position) == Containment::Intersects {
                         edge_tiles.push(position);
                     }
                 }
             }
-            for x in (map.left() + 1)..map.right() {
-                let position = Position::new(x, map.bottom());
-                if map.contains_position(position) == Containment::Intersects {
-                    edge_tiles.push(position);
+            if self.allows_wall(CardinalDirection::South) {
+                for x in (map.left() + 1)..map.right() {
+                    let position = Position::new(x, map.bottom());
+                    if map.contains_position(position) == Containment::Intersects {
+                        edge_tiles.push(position);
+                    }
                 }
             }
 
             let count = self.provides_count.provide_count();
-            let mut rng = thread_rng();
+            let mut rng = dun_gen_rng();
             for _ in 0..count {
+                if edge_tiles.is_empty() {
+                    break;
+                }
+
                 let index = rng.gen_range(0, edge_tiles.len());
-                let edge_portal_position = edge_tiles[index];
-                edge_tiles.truncate(edge_tiles.len() - 1);
+                let edge_portal_position = edge_tiles.swap_remove(index);
                 data.push((
                     edge_portal_position,
                     if edge_portal_position.x() == map.left() {
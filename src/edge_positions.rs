@@ -0,0 +1,101 @@
+// External includes.
+use rand::distributions::Distribution;
+use rand::Rng;
+
+// Standard includes.
+
+// Internal includes.
+use crate::dun_gen_rng::dun_gen_rng;
+use crate::geometry::*;
+
+/// Provides uniformly random positions on the edge of an [`Area`](geometry/struct.Area.html), excluding its corners, for placing doors, portals, or decorations.
+///
+/// Each position comes with the [`CardinalDirection`](geometry/enum.CardinalDirection.html) pointing from its edge into the area, as used by [`EdgePortalsGenerator`](struct.EdgePortalsGenerator.html) for the facing of its portals; `CardinalDirection::South` for the top edge, `CardinalDirection::North` for the bottom edge, `CardinalDirection::East` for the left edge, and `CardinalDirection::West` for the right edge.
+///
+/// Panics on creation if the area is less than 3 tiles wide or 3 tiles high, as it then has no edge positions which are not corners.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let area = Area::new(Position::new(2, 3), Size::new(6, 4));
+/// let edge_positions = EdgePositions::new(area);
+/// let (left, top, right, bottom) = (2, 3, 7, 6);
+///
+/// for _ in 0..1000 {
+///     let (position, facing) = edge_positions.provide_position_and_facing();
+///     let on_edges = [
+///         (position.y() == top, CardinalDirection::South),
+///         (position.y() == bottom, CardinalDirection::North),
+///         (position.x() == left, CardinalDirection::East),
+///         (position.x() == right, CardinalDirection::West),
+///     ];
+///
+///     // Exactly one edge, so never a corner.
+///     assert!(on_edges.iter().filter(|(on_edge, _)| *on_edge).count() == 1);
+///     assert!(on_edges.iter().any(|(on_edge, edge_facing)| *on_edge && *edge_facing == facing));
+///     assert!(position.x() >= left && position.x() <= right);
+///     assert!(position.y() >= top && position.y() <= bottom);
+/// }
+///
+/// let position = edge_positions.provide_position();
+/// assert!(position.x() == left || position.x() == right || position.y() == top || position.y() == bottom);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct EdgePositions {
+    area: Area,
+}
+
+impl EdgePositions {
+    /// Creates a new `EdgePositions` for the given area.
+    pub fn new(area: Area) -> Self {
+        assert!(
+            area.size().width() >= 3 && area.size().height() >= 3,
+            "EdgePositions requires an area at least 3 tiles wide and 3 tiles high."
+        );
+
+        Self { area }
+    }
+
+    /// Returns a random position on the edge of the area, excluding its corners, and the direction pointing from that edge into the area.
+    pub fn provide_position_and_facing(&self) -> (Position, CardinalDirection) {
+        self.sample(&mut dun_gen_rng())
+    }
+}
+
+impl Distribution<(Position, CardinalDirection)> for EdgePositions {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (Position, CardinalDirection) {
+        let (left, top) = (self.area.position().x(), self.area.position().y());
+        // The number of tiles on each edge, excluding the corners.
+        let horizontal = self.area.size().width() as i32 - 2;
+        let vertical = self.area.size().height() as i32 - 2;
+        let (right, bottom) = (left + horizontal + 1, top + vertical + 1);
+
+        let index = rng.gen_range(0, (horizontal + vertical) * 2);
+        if index < horizontal {
+            (
+                Position::new(left + 1 + index, top),
+                CardinalDirection::South,
+            )
+        } else if index < horizontal * 2 {
+            (
+                Position::new(left + 1 + index - horizontal, bottom),
+                CardinalDirection::North,
+            )
+        } else if index < horizontal * 2 + vertical {
+            (
+                Position::new(left, top + 1 + index - horizontal * 2),
+                CardinalDirection::East,
+            )
+        } else {
+            (
+                Position::new(right, top + 1 + index - horizontal * 2 - vertical),
+                CardinalDirection::West,
+            )
+        }
+    }
+}
+
+impl ProvidesPosition for EdgePositions {
+    fn provide_position(&self) -> Position {
+        self.provide_position_and_facing().0
+    }
+}
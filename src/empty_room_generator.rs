@@ -44,11 +44,43 @@ use crate::geometry::*;
 /// }
 /// assert!(count == 0);
 /// ```
+///
+/// By default the generator grows the map to fit the room, but never shrinks it. [`EmptyRoomGenerator::with_mode()`](#method.with_mode) takes an [`EmptyRoomMode`](enum.EmptyRoomMode.html) to instead fill only within the map's current size, or to resize the map to fit the room exactly.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let modes = [EmptyRoomMode::GrowOnly, EmptyRoomMode::WithinBounds, EmptyRoomMode::Exact];
+/// let mut results = Vec::new();
+/// for mode in modes.iter() {
+///     for existing_size in [Size::new(4, 3), Size::new(12, 10)].iter() {
+///         let map_id =
+///             DunGen::new(SparseMap::new())
+///             .gen_with(FillTilesGenerator::new(*existing_size, TileType::Wall))
+///             .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)).with_mode(*mode))
+///             .build();
+///
+///         let maps = MAPS.read();
+///         let map = maps[map_id].read();
+///         results.push((*map.size(), map.count_tiles(TileType::Floor)));
+///     }
+/// }
+///
+/// // Grow only: the smaller map grows to fit the room, and the larger map keeps its size.
+/// assert!(results[0] == (Size::new(8, 6), 8 * 6));
+/// assert!(results[1] == (Size::new(12, 10), 8 * 6));
+/// // Within bounds: the room is cut down to the smaller map, and neither map changes size.
+/// assert!(results[2] == (Size::new(4, 3), 4 * 3));
+/// assert!(results[3] == (Size::new(12, 10), 8 * 6));
+/// // Exact: both maps are resized to the room.
+/// assert!(results[4] == (Size::new(8, 6), 8 * 6));
+/// assert!(results[5] == (Size::new(8, 6), 8 * 6));
+/// ```
 pub struct EmptyRoomGenerator<TProvidesPlacedShape>
 where
     TProvidesPlacedShape: ProvidesPlacedShape + Sized,
 {
     forward_to: FillTilesGenerator<TProvidesPlacedShape>,
+    mode: EmptyRoomMode,
 }
 
 impl<TProvidesPlacedShape> EmptyRoomGenerator<TProvidesPlacedShape>
@@ -59,8 +91,15 @@ where
     pub fn new(provides_placed_shape: TProvidesPlacedShape) -> Self {
         Self {
             forward_to: FillTilesGenerator::new(provides_placed_shape, TileType::Floor),
+            mode: EmptyRoomMode::GrowOnly,
         }
     }
+
+    /// Sets how the generator treats the existing size of the map.
+    pub fn with_mode(mut self, mode: EmptyRoomMode) -> Self {
+        self.mode = mode;
+        self
+    }
 }
 
 impl<TProvidesPlacedShape> DoesDunGen for EmptyRoomGenerator<TProvidesPlacedShape>
@@ -68,10 +107,28 @@ where
     TProvidesPlacedShape: ProvidesPlacedShape + Sized,
 {
     fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
-        self.forward_to.dun_gen(target)
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
     }
 
     fn dun_gen_map(&self, map_id: MapId) {
-        self.forward_to.dun_gen_map(map_id)
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+        let shape = match self.forward_to.placed_shape(*map.size()) {
+            Some(shape) => shape,
+            None => return,
+        };
+
+        match self.mode {
+            EmptyRoomMode::GrowOnly => self.forward_to.fill_shape(&mut ***map, &*shape, false),
+            EmptyRoomMode::WithinBounds => self.forward_to.fill_shape(&mut ***map, &*shape, true),
+            EmptyRoomMode::Exact => {
+                map.resize(Size::new(
+                    (shape.right() + 1).max(0) as u32,
+                    (shape.bottom() + 1).max(0) as u32,
+                ));
+                self.forward_to.fill_shape(&mut ***map, &*shape, true);
+            }
+        }
     }
 }
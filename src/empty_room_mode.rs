@@ -0,0 +1,16 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+
+/// How an [`EmptyRoomGenerator`](struct.EmptyRoomGenerator.html) treats the existing [`Size`](geometry/struct.Size.html) of the map.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum EmptyRoomMode {
+    /// Fills the whole room, growing the map to fit it if needed; the map never shrinks. This is the default.
+    GrowOnly,
+    /// Fills only the part of the room within the map's current size; the map's size never changes.
+    WithinBounds,
+    /// Resizes the map to end exactly at the room's right and bottom edges, dropping any tiles beyond them, and then fills the room.
+    Exact,
+}
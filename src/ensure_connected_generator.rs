@@ -0,0 +1,113 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for joining every region of floor into one, by carving the shortest corridors between them.
+///
+/// The `EnsureConnectedGenerator` finds each group of orthogonally connected [`TileType`](enum.TileType.html)::Floor tiles on the map. While there is more than one group, it finds the two tiles in different groups which are the fewest orthogonal steps apart, and carves an L-shaped corridor of floor between them, going horizontally first. Useful as a fixup after cave generation, which can leave pockets of floor cut off from each other; to remove the pockets instead, use [`RemoveIsolatedFloorsGenerator`](struct.RemoveIsolatedFloorsGenerator.html).
+///
+/// Will create a cave with two separate chambers, 4 tiles apart, and join them with a 3-tile corridor.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(12, 8), TileType::Wall))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(1, 1), Size::new(3, 3)),
+///         TileType::Floor))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(7, 2), Size::new(3, 4)),
+///         TileType::Floor))
+///     .gen_with(EnsureConnectedGenerator::new())
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(map.floor_connected_components(Connectivity::Four).len() == 1);
+/// // The bridge is only as long as the gap between the chambers.
+/// assert!(map.count_tiles(TileType::Floor) == (3 * 3) + (3 * 4) + 3);
+/// for x in 4..7 {
+///     assert!(map.tile_type_at_local(Position::new(x, 2)) == Some(TileType::Floor));
+/// }
+/// ```
+pub struct EnsureConnectedGenerator {}
+
+impl EnsureConnectedGenerator {
+    /// Creates a new generator for joining every region of floor into one.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl DoesDunGen for EnsureConnectedGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let neighbor_offsets = Connectivity::Four.neighbor_offsets();
+        loop {
+            let components = map.floor_connected_components(Connectivity::Four);
+            if components.len() <= 1 {
+                return;
+            }
+
+            // Only the tiles on the edge of a region can be the nearest to another region.
+            let edges = components
+                .iter()
+                .map(|component| {
+                    component
+                        .iter()
+                        .copied()
+                        .filter(|position| {
+                            neighbor_offsets.iter().any(|offset| {
+                                map.tile_type_at_local(*position + *offset) != Some(TileType::Floor)
+                            })
+                        })
+                        .collect::<Vec<Position>>()
+                })
+                .collect::<Vec<Vec<Position>>>();
+
+            let mut nearest: Option<(i32, Position, Position)> = None;
+            for (index, edge) in edges.iter().enumerate() {
+                for other_edge in edges.iter().skip(index + 1) {
+                    for from in edge {
+                        for to in other_edge {
+                            let distance = (to.x() - from.x()).abs() + (to.y() - from.y()).abs();
+                            if !matches!(
+                                nearest,
+                                Some((nearest_distance, _, _)) if nearest_distance <= distance
+                            ) {
+                                nearest = Some((distance, *from, *to));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let (_, from, to) = nearest.unwrap();
+            let step_x = (to.x() - from.x()).signum();
+            let step_y = (to.y() - from.y()).signum();
+            let mut position = from;
+            while position.x() != to.x() {
+                position = Position::new(position.x() + step_x, position.y());
+                map.tile_type_at_local_set(position, TileType::Floor);
+            }
+            while position.y() != to.y() {
+                position = Position::new(position.x(), position.y() + step_y);
+                map.tile_type_at_local_set(position, TileType::Floor);
+            }
+        }
+    }
+}
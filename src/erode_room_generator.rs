@@ -0,0 +1,82 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for thinning the floor of a map.
+///
+/// For each iteration, the `ErodeRoomGenerator` converts every [`TileType`](enum.TileType.html)::Floor tile that has any orthogonal neighbour which is not `TileType::Floor` into `TileType::Wall`. Neighbours outside of the map count as not being floor. Each iteration works from the tiles as they were at the start of that iteration, so the floor shrinks by exactly one tile per iteration; this removes narrow passages, and smooths out jagged cave walls.
+///
+/// Will create a floor 3 tiles wide by 3 tiles high, and erode it once, leaving only the centre tile as floor.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(3, 3)))
+///     .gen_with(ErodeRoomGenerator::new(1))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(map.count_tiles(TileType::Floor) == 1);
+/// assert!(map.count_tiles(TileType::Wall) == 8);
+/// assert!(map.tile_type_at_local(Position::new(1, 1)) == Some(TileType::Floor));
+/// ```
+pub struct ErodeRoomGenerator {
+    iterations: usize,
+}
+
+impl ErodeRoomGenerator {
+    /// Creates a new generator for thinning the floor of a map by the given number of iterations.
+    pub fn new(iterations: usize) -> Self {
+        Self { iterations }
+    }
+}
+
+impl DoesDunGen for ErodeRoomGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        for _ in 0..self.iterations {
+            let mut eroded = Vec::new();
+            map.visit_tiles(|position, tile_type| {
+                if tile_type != TileType::Floor {
+                    return;
+                }
+
+                let next_to_non_floor = [
+                    Position::NORTH,
+                    Position::EAST,
+                    Position::SOUTH,
+                    Position::WEST,
+                ]
+                .iter()
+                .any(|direction| {
+                    map.tile_type_at_local(position + *direction) != Some(TileType::Floor)
+                });
+                if next_to_non_floor {
+                    eroded.push(position);
+                }
+            });
+
+            if eroded.is_empty() {
+                break;
+            }
+
+            for position in eroded {
+                map.tile_type_at_local_set(position, TileType::Wall);
+            }
+        }
+    }
+}
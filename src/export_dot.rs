@@ -0,0 +1,69 @@
+// External includes.
+
+// Standard includes.
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Write;
+
+// Internal includes.
+use super::*;
+
+/// Returns the graph of [`Portal`](struct.Portal.html)s reachable from the given map, in the Graphviz DOT format, for visualising the layout of a dungeon.
+///
+/// Each reachable map is a node, named by its `MapId`, and each portal is an edge from the map it is on to its target, labeled with the direction the portal faces. Maps are visited breadth-first, and each map is only visited once, even if the portals form cycles.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 8)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(4, Box::new(|| SparseMap::new())))
+///     .gen_with(TraversePortalsGenerator::new(SequentialGenerator::new(&[
+///         &EmptyRoomGenerator::new(Size::new(4, 4)),
+///         &WalledRoomGenerator::new(Size::zero()),
+///     ])))
+///     .gen_with(TraverseThisAndPortalsGenerator::new(ReciprocatePortalsGenerator::new()))
+///     .build();
+///
+/// let dot = export_dot(map_id);
+/// assert!(dot.starts_with("digraph dungeon {"));
+/// assert!(dot.trim_end().ends_with('}'));
+///
+/// // The hub, and its 4 spokes.
+/// let nodes = dot.lines().filter(|line| line.ends_with(';') && !line.contains("->"));
+/// assert!(nodes.count() == 5);
+/// // A portal out to each spoke, and a portal back from each spoke.
+/// let edges = dot.lines().filter(|line| line.contains("->"));
+/// assert!(edges.count() == 8);
+/// assert!(dot.contains(&format!("    {};", map_id)));
+/// ```
+pub fn export_dot(root: MapId) -> String {
+    let maps = &MAPS.read();
+    let mut visited = HashSet::new();
+    let mut on_maps = VecDeque::new();
+    let mut nodes = String::new();
+    let mut edges = String::new();
+    visited.insert(root);
+    on_maps.push_back(root);
+
+    while let Some(map_id) = on_maps.pop_front() {
+        writeln!(nodes, "    {};", map_id).unwrap();
+        let map = &maps[map_id].read();
+        for portal in map.portals() {
+            let target_map_id = portal.target();
+            writeln!(
+                edges,
+                "    {} -> {} [label=\"{}\"];",
+                map_id,
+                target_map_id,
+                portal.portal_to_map_facing()
+            )
+            .unwrap();
+            if visited.insert(target_map_id) {
+                on_maps.push_back(target_map_id);
+            }
+        }
+    }
+
+    format!("digraph dungeon {{\n{}{}}}\n", nodes, edges)
+}
@@ -0,0 +1,89 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for placing a feature on the floor tile farthest from the portals of a map; such as the exit, far from the entrance.
+///
+/// The `FarthestFloorGenerator` measures the walking distance from the map's [`Portal`](struct.Portal.html)s with a [`DistanceFieldGenerator`](struct.DistanceFieldGenerator.html), and sets the reachable [`TileType`](enum.TileType.html)::Floor tile with the greatest distance to the given `TileType`. If several tiles are equally far, the first of them, in row order, is chosen. If the map has no portals, or no floor can be reached from them, the map is left as-is.
+///
+/// Will create a walled room 12 tiles wide by 8 tiles high, with a portal on its west wall, and mark the floor tile farthest from the portal.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 8)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .build();
+/// let target_map_id = SparseMap::new();
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     map.add_portal(Position::new(0, 2), CardinalDirection::East, Position::zero(), target_map_id);
+/// }
+///
+/// let distance_field_generator = DistanceFieldGenerator::from_portals();
+/// distance_field_generator.dun_gen_map(map_id);
+/// let distances = distance_field_generator.distances(map_id);
+///
+/// DunGen::new(map_id)
+///     .gen_with(FarthestFloorGenerator::new(TileType::Void));
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// let max_distance = distances.values().max().unwrap();
+/// assert!(distances[&Position::new(10, 6)] == *max_distance);
+/// assert!(map.tile_type_at_local(Position::new(10, 6)).unwrap_or(TileType::Void) == TileType::Void);
+/// assert!(map.count_tiles(TileType::Floor) == (10 * 6) - 1);
+/// ```
+pub struct FarthestFloorGenerator {
+    target: TileType,
+}
+
+impl FarthestFloorGenerator {
+    /// Creates a new generator for setting the floor tile farthest from the portals of a map to the given `TileType`.
+    pub fn new(target: TileType) -> Self {
+        Self { target }
+    }
+}
+
+impl DoesDunGen for FarthestFloorGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let distance_field_generator = DistanceFieldGenerator::from_portals();
+        distance_field_generator.dun_gen_map(map_id);
+        let distances = distance_field_generator.distances(map_id);
+
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let mut farthest: Option<(Position, u32)> = None;
+        map.visit_tiles(|position, tile_type| {
+            if tile_type != TileType::Floor {
+                return;
+            }
+
+            if let Some(distance) = distances.get(&position) {
+                let is_farther = match farthest {
+                    Some((_, farthest_distance)) => *distance > farthest_distance,
+                    None => true,
+                };
+                if is_farther {
+                    farthest = Some((position, *distance));
+                }
+            }
+        });
+
+        if let Some((position, _)) = farthest {
+            map.tile_type_at_local_set(position, self.target);
+        }
+    }
+}
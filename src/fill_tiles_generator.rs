@@ -4,6 +4,7 @@
 
 // Internal includes.
 use super::*;
+use crate::fractional_area::provide_placed_shape_within;
 use crate::geometry::*;
 
 /// A generator for filling an area with a [`TileType`](enum.TileType.html).
@@ -59,6 +60,39 @@ use crate::geometry::*;
 /// }
 /// assert!(count == 0);
 /// ```
+///
+/// The tiles are written with [`MapExt::set_tiles()`](trait.MapExt.html#method.set_tiles); filling a large map gives the same result as setting each tile in turn.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let filled_map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(128, 128), TileType::Wall))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(16, 8), Size::new(64, 100)),
+///         TileType::Floor))
+///     .build();
+/// let set_map_id = SparseMap::new();
+///
+/// let maps = MAPS.read();
+/// let filled_map = maps[filled_map_id].read();
+/// let mut set_map = maps[set_map_id].write();
+/// for y in 0..128 {
+///     for x in 0..128 {
+///         let inside = x >= 16 && x < 16 + 64 && y >= 8 && y < 8 + 100;
+///         let tile_type = if inside { TileType::Floor } else { TileType::Wall };
+///         set_map.tile_type_at_local_set(Position::new(x, y), tile_type);
+///     }
+/// }
+///
+/// assert!(filled_map.size() == set_map.size());
+/// for y in 0..128 {
+///     for x in 0..128 {
+///         let position = Position::new(x, y);
+///         assert!(filled_map.tile_type_at_local(position) == set_map.tile_type_at_local(position));
+///     }
+/// }
+/// ```
 pub struct FillTilesGenerator<TProvidesPlacedShape>
 where
     TProvidesPlacedShape: ProvidesPlacedShape + Sized,
@@ -78,22 +112,11 @@ where
             tile_type_fill,
         }
     }
-}
-
-impl<TProvidesPlacedShape> DoesDunGen for FillTilesGenerator<TProvidesPlacedShape>
-where
-    TProvidesPlacedShape: ProvidesPlacedShape + Sized,
-{
-    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
-        let map_id = target.get_map_id();
-        self.dun_gen_map(map_id);
-    }
 
-    fn dun_gen_map(&self, map_id: MapId) {
-        let shape = self.provides_placed_shape.provide_placed_shape();
-        let maps = &MAPS.read();
-        let map = &mut maps[map_id].write();
-        let possible_area = Area::from(*map.size());
+    /// Returns the shape to fill in a map of the given size, or `None` if there is nothing to fill.
+    pub(crate) fn placed_shape(&self, map_size: Size) -> Option<Box<dyn PlacedShape>> {
+        let possible_area = Area::from(map_size);
+        let shape = provide_placed_shape_within(&self.provides_placed_shape, possible_area);
         let shape = if shape.width() > 0 || shape.height() > 0 {
             shape
         } else {
@@ -101,16 +124,49 @@ where
         };
 
         if *shape.size() == Size::zero() {
-            return;
+            None
+        } else {
+            Some(shape)
         }
+    }
 
+    /// Fills the given shape in the map; if `within_size` is true, tiles outside of the map's current size are skipped, instead of growing the map.
+    pub(crate) fn fill_shape(&self, map: &mut dyn Map, shape: &dyn PlacedShape, within_size: bool) {
+        let size = *map.size();
+        let mut cells = Vec::new();
         for y in shape.top()..=shape.bottom() {
             for x in shape.left()..=shape.right() {
                 let position = Position::new(x, y);
-                if shape.intersects_position(position) {
-                    map.tile_type_at_local_set(position, self.tile_type_fill);
+                if within_size
+                    && (x < 0 || y < 0 || x as u32 >= size.width() || y as u32 >= size.height())
+                {
+                    continue;
+                }
+
+                if shape.intersects_position(position) && map.is_in_bounds(position) {
+                    cells.push((position, self.tile_type_fill));
                 }
             }
         }
+
+        map.set_tiles(cells);
+    }
+}
+
+impl<TProvidesPlacedShape> DoesDunGen for FillTilesGenerator<TProvidesPlacedShape>
+where
+    TProvidesPlacedShape: ProvidesPlacedShape + Sized,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+        if let Some(shape) = self.placed_shape(*map.size()) {
+            self.fill_shape(&mut ***map, &*shape, false);
+        }
     }
 }
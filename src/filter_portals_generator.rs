@@ -0,0 +1,116 @@
+// External includes.
+
+// Standard includes.
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+// Internal includes.
+use super::*;
+
+/// Used to execute a dungeon generator by traversing only the portals which match a predicate.
+///
+/// Like [`TraversePortalsGenerator`](struct.TraversePortalsGenerator.html), but each [`Portal`](struct.Portal.html) is only traversed, and its target map generated, if the predicate returns `true` for it. Pairs well with [`PortalExt::label()`](trait.PortalExt.html#tymethod.label).
+///
+/// The following code will add a portal facing each direction to a map, and then generate rooms only at the ends of the north-facing portals.
+///```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 8)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .build();
+///
+/// let facings = [
+///     (Position::new(5, 7), CardinalDirection::North),
+///     (Position::new(0, 3), CardinalDirection::East),
+///     (Position::new(5, 0), CardinalDirection::South),
+///     (Position::new(11, 3), CardinalDirection::West),
+/// ];
+/// let target_map_ids = facings.iter().map(|_| SparseMap::new()).collect::<Vec<_>>();
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     for ((local_position, facing), target_map_id) in facings.iter().zip(&target_map_ids) {
+///         map.add_portal(*local_position, *facing, Position::zero(), *target_map_id);
+///     }
+/// }
+///
+/// FilterPortalsGenerator::new(
+///     |portal: &Portal| *portal.portal_to_map_facing() == CardinalDirection::North,
+///     EmptyRoomGenerator::new(Size::new(8, 6)),
+/// )
+/// .dun_gen_map(map_id);
+///
+/// let maps = MAPS.read();
+/// for ((_, facing), target_map_id) in facings.iter().zip(&target_map_ids) {
+///     let target_map = maps[*target_map_id].read();
+///     if *facing == CardinalDirection::North {
+///         assert!(*target_map.size() == Size::new(8, 6));
+///     } else {
+///         assert!(*target_map.size() == Size::zero());
+///     }
+/// }
+///```
+pub struct FilterPortalsGenerator<TDunGen, TPortalFilter>
+where
+    TDunGen: DoesDunGen,
+    TPortalFilter: Fn(&Portal) -> bool,
+{
+    portal_filter: TPortalFilter,
+    dun_gen: TDunGen,
+    visited_maps: RwLock<HashSet<MapId>>,
+}
+
+impl<TDunGen, TPortalFilter> FilterPortalsGenerator<TDunGen, TPortalFilter>
+where
+    TDunGen: DoesDunGen,
+    TPortalFilter: Fn(&Portal) -> bool,
+{
+    /// Creates a dungeon generator that traverses the portals matching the filter.
+    pub fn new(portal_filter: TPortalFilter, dun_gen: TDunGen) -> Self {
+        Self {
+            portal_filter,
+            dun_gen,
+            visited_maps: RwLock::new(HashSet::new()),
+        }
+    }
+}
+
+impl<TDunGen, TPortalFilter> DoesDunGen for FilterPortalsGenerator<TDunGen, TPortalFilter>
+where
+    TDunGen: DoesDunGen,
+    TPortalFilter: Fn(&Portal) -> bool,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        {
+            let mut visited_maps = self.visited_maps.write().unwrap();
+            if visited_maps.contains(&map_id) {
+                return;
+            }
+
+            visited_maps.insert(map_id);
+        }
+
+        let mut target_map_ids = Vec::new();
+        {
+            let maps = &MAPS.read();
+            let map = &maps[map_id].read();
+            for portal in map.portals() {
+                if (self.portal_filter)(portal) {
+                    target_map_ids.push(portal.target());
+                }
+            }
+        }
+
+        for target_map_id in target_map_ids {
+            self.dun_gen_map(target_map_id);
+            self.dun_gen.dun_gen_map(target_map_id);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// External includes.
+
+// Standard includes.
+use std::cell::Cell;
+
+// Internal includes.
+use crate::geometry::*;
+
+thread_local! {
+    /// The area of the map currently being generated, which a `FractionalArea` is sized against; `None` outside of `provide_placed_shape_within()`.
+    static OUTER_AREA: Cell<Option<Area>> = const { Cell::new(None) };
+}
+
+/// Provides the shape from the given provider, with any [`FractionalArea`](struct.FractionalArea.html) sized against the given outer area.
+///
+/// Generators which take a [`ProvidesPlacedShape`](geometry/trait.ProvidesPlacedShape.html), and place the shape within a map, should provide it through this function, with the map's area, so that a `FractionalArea` given to them is sized against the map.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let outer_area = Area::new(Position::new(10, 10), Size::new(40, 20));
+/// let shape = provide_placed_shape_within(&FractionalArea::new(0.5, 0.5, true), outer_area);
+/// assert!(*shape.area() == Area::new(Position::new(20, 15), Size::new(20, 10)));
+///
+/// // Other providers are unaffected.
+/// let shape = provide_placed_shape_within(&Size::new(3, 4), outer_area);
+/// assert!(*shape.size() == Size::new(3, 4));
+/// ```
+pub fn provide_placed_shape_within<TProvidesPlacedShape>(
+    provides_placed_shape: &TProvidesPlacedShape,
+    outer_area: Area,
+) -> Box<dyn PlacedShape>
+where
+    TProvidesPlacedShape: ProvidesPlacedShape + ?Sized,
+{
+    let previous = OUTER_AREA.with(|area| area.replace(Some(outer_area)));
+    let shape = provides_placed_shape.provide_placed_shape();
+    OUTER_AREA.with(|area| area.set(previous));
+
+    shape
+}
+
+/// Provides an [`Area`](geometry/struct.Area.html) sized as a fraction of the map it is used on, rather than with a fixed [`Size`](geometry/struct.Size.html).
+///
+/// The area is sized when a generator asks for it, against the map's current size, so the same `FractionalArea` fits maps of any size; for example, maps given a random size by a [`SizeRange`](geometry/struct.SizeRange.html). The width and height are rounded to the nearest tile, but are never less than 1 tile on a map which is not empty. A centered area is placed in the middle of the map; otherwise, it is placed in the top-left corner.
+///
+/// A `FractionalArea` has no size of its own; it is sized against the area passed to [`provide_placed_shape_within()`](fn.provide_placed_shape_within.html), which [`FillTilesGenerator`](struct.FillTilesGenerator.html), [`EmptyRoomGenerator`](struct.EmptyRoomGenerator.html), [`WalledRoomGenerator`](struct.WalledRoomGenerator.html), and [`ReplaceTilesGenerator`](struct.ReplaceTilesGenerator.html) call with the map's area; generators of your own should do the same. Its `provide_placed_shape()` called anywhere else has no area to size against; it panics in debug builds, and provides an empty area at the origin in release builds. To size it against an area directly, use [`FractionalArea::area_within()`](#method.area_within).
+///
+/// Will create a map 100 tiles wide by 50 tiles high, filled with wall, and carve a room 60% of its width and 40% of its height out of its middle.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(100, 50), TileType::Wall))
+///     .gen_with(EmptyRoomGenerator::new(FractionalArea::new(0.6, 0.4, true)))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(*map.size() == Size::new(100, 50));
+/// assert!(map.count_tiles(TileType::Floor) == 60 * 20);
+/// assert!(map.tile_type_at_local(Position::new(20, 15)) == Some(TileType::Floor));
+/// assert!(map.tile_type_at_local(Position::new(79, 34)) == Some(TileType::Floor));
+/// assert!(map.tile_type_at_local(Position::new(19, 15)) == Some(TileType::Wall));
+/// assert!(map.tile_type_at_local(Position::new(20, 14)) == Some(TileType::Wall));
+/// assert!(map.tile_type_at_local(Position::new(80, 34)) == Some(TileType::Wall));
+/// assert!(map.tile_type_at_local(Position::new(79, 35)) == Some(TileType::Wall));
+/// ```
+///
+/// Will carve a room 1% of the width and height of a map 40 tiles wide by 30 tiles high; the room is rounded up to a single tile, rather than to nothing.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(40, 30), TileType::Wall))
+///     .gen_with(EmptyRoomGenerator::new(FractionalArea::new(0.01, 0.01, true)))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(map.count_tiles(TileType::Floor) == 1);
+/// assert!(map.tile_type_at_local(Position::new(19, 14)) == Some(TileType::Floor));
+/// assert!(map.count_tiles(TileType::Wall) == (40 * 30) - 1);
+///
+/// let fractional_area = FractionalArea::new(0.5, 0.01, false);
+/// assert!(*fractional_area.area_within(Area::from(Size::new(40, 30))).size() == Size::new(20, 1));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FractionalArea {
+    width_frac: f64,
+    height_frac: f64,
+    centered: bool,
+}
+
+impl FractionalArea {
+    /// Creates a new `FractionalArea` with the given fractions of the map's width and height, each in the range [0, 1].
+    pub fn new(width_frac: f64, height_frac: f64, centered: bool) -> Self {
+        Self {
+            width_frac: width_frac.clamp(0.0, 1.0),
+            height_frac: height_frac.clamp(0.0, 1.0),
+            centered,
+        }
+    }
+
+    /// Returns the area, sized as a fraction of the given outer area; each of its width and height is at least 1 tile, unless the outer area's is 0.
+    pub fn area_within(&self, outer_area: Area) -> Area {
+        let outer_width = outer_area.size().width();
+        let outer_height = outer_area.size().height();
+        // Rounding to no tiles would have the generators fill the whole map instead.
+        let width = ((outer_width as f64 * self.width_frac).round() as u32)
+            .clamp(outer_width.min(1), outer_width);
+        let height = ((outer_height as f64 * self.height_frac).round() as u32)
+            .clamp(outer_height.min(1), outer_height);
+        let position = if self.centered {
+            *outer_area.position()
+                + Position::new(
+                    ((outer_width - width) / 2) as i32,
+                    ((outer_height - height) / 2) as i32,
+                )
+        } else {
+            *outer_area.position()
+        };
+
+        Area::new(position, Size::new(width, height))
+    }
+}
+
+impl ProvidesPlacedShape for FractionalArea {
+    fn provide_placed_shape(&self) -> Box<dyn PlacedShape> {
+        let outer_area = OUTER_AREA.with(|area| area.get());
+        debug_assert!(
+            outer_area.is_some(),
+            "FractionalArea must be provided through provide_placed_shape_within(), so that it has an area to be sized against."
+        );
+        let outer_area = outer_area.unwrap_or_else(|| Area::new(Position::zero(), Size::zero()));
+        self.area_within(outer_area).provide_placed_shape()
+    }
+}
@@ -0,0 +1,61 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::default_tile_map::DEFAULT_TILES;
+use crate::map_ext::{OUT_OF_BOUNDS, TILE_ATTRS};
+use crate::portal_ext::PORTAL_LABELS;
+
+/// Frees the specified map, so that its slot in [`MAPS`](struct.MAPS.html) is reused by the next new map.
+///
+/// Calls [`invalidate_map()`](fn.invalidate_map.html), and also removes everything this crate stores on the side for the map, such as the labels of its [`Portal`](struct.Portal.html)s, the attributes and bounds mask of its tiles, and its default tile. Generators which discard maps, such as [`RetryGenerator`](struct.RetryGenerator.html) and [`SubMapGenerator`](struct.SubMapGenerator.html), free them with this function, so that the number of maps in use stays bounded however many attempts they make.
+///
+/// As with `invalidate_map()`, this should only be used when there are no other instances of the given `MapId`.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let maps_before = maps_in_use();
+/// for _ in 0..10000 {
+///     let map_id =
+///         DunGen::new(SparseMap::new())
+///         .gen_with(EmptyRoomGenerator::new(Size::new(4, 4)))
+///         .build();
+///     free_map(map_id);
+/// }
+///
+/// assert!(maps_in_use() <= maps_before + 1);
+/// assert!(MAPS.read().len() <= maps_before + 2);
+/// ```
+pub fn free_map(map_id: MapId) {
+    PORTAL_LABELS.write().unwrap().remove(&map_id);
+    TILE_ATTRS.write().unwrap().remove(&map_id);
+    OUT_OF_BOUNDS.write().unwrap().remove(&map_id);
+    DEFAULT_TILES.write().unwrap().remove(&map_id);
+
+    invalidate_map(map_id);
+}
+
+/// Returns the number of maps in [`MAPS`](struct.MAPS.html) which are in use; that is, which have not been invalidated or freed.
+///
+/// Useful to check that a long-running generation session is not leaking maps.
+/// ```
+/// # use dungen_minion::*;
+/// let _root_map_id = SparseMap::new();
+/// let maps_before = maps_in_use();
+/// let map_id = SparseMap::new();
+/// assert!(maps_in_use() == maps_before + 1);
+///
+/// free_map(map_id);
+/// assert!(maps_in_use() == maps_before);
+/// ```
+pub fn maps_in_use() -> usize {
+    // A slot which has been invalidated holds a placeholder map with a `MapId` of 0, which will not
+    // match the slot's index. Slot 0 cannot be told apart in this way, and so is always counted.
+    let maps = &MAPS.read();
+    maps.iter()
+        .enumerate()
+        .filter(|(map_id, map)| *map_id == 0 || map.read().map_id() == *map_id)
+        .count()
+}
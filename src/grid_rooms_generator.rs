@@ -0,0 +1,153 @@
+// External includes.
+
+// Standard includes.
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for laying out a regular grid of rooms across a map, connected by corridors.
+///
+/// The `GridRoomsGenerator` divides the map's [`Size`](geometry/struct.Size.html) into a grid of equal cells, with the given number of rows and columns, and carves a room of [`TileType`](enum.TileType.html)::Floor in the middle of each cell, leaving the given margin between the room and the edges of the cell. A room is never smaller than 1 tile by 1 tile. Each room is then joined to the rooms beside it, and to the rooms above and below it, by a straight corridor 1 tile wide running between their centres. The rest of the map is left as-is.
+///
+/// The [`Area`](geometry/struct.Area.html)s of the rooms are stored per [`MapId`](type.MapId.html), and can be retrieved after generation through [`GridRoomsGenerator::areas()`](#method.areas).
+///
+/// Will create a map 30 tiles wide by 30 tiles high, filled with wall, and carve out a 3 by 3 grid of rooms in it.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let rooms_generator = GridRoomsGenerator::new(3, 3, 2);
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(30, 30), TileType::Wall))
+///     .build();
+/// rooms_generator.dun_gen_map(map_id);
+///
+/// let areas = rooms_generator.areas(map_id);
+/// assert!(areas.len() == 9);
+/// assert!(areas[0] == Area::new(Position::new(2, 2), Size::new(6, 6)));
+/// assert!(areas[8] == Area::new(Position::new(22, 22), Size::new(6, 6)));
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// // Every room, and every corridor, can be reached from the first room.
+/// let mut reached = std::collections::HashSet::new();
+/// let mut open = vec![Position::new(2, 2)];
+/// reached.insert(Position::new(2, 2));
+/// while let Some(position) = open.pop() {
+///     for neighbor in map.walkable_neighbors(position) {
+///         if reached.insert(neighbor) {
+///             open.push(neighbor);
+///         }
+///     }
+/// }
+/// assert!(reached.len() == map.count_tiles(TileType::Floor));
+/// for area in &areas {
+///     assert!(reached.contains(area.position()));
+/// }
+/// ```
+pub struct GridRoomsGenerator {
+    rows: u32,
+    cols: u32,
+    room_margin: u32,
+    areas: RwLock<HashMap<MapId, Vec<Area>>>,
+}
+
+impl GridRoomsGenerator {
+    /// Creates a new generator for laying out a grid of rooms with the given number of rows and columns.
+    pub fn new(rows: u32, cols: u32, room_margin: u32) -> Self {
+        Self {
+            rows,
+            cols,
+            room_margin,
+            areas: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the areas of the rooms laid out on the map with the given `MapId`, in row-major order. Returns an empty `Vec` if no rooms were laid out on the map.
+    pub fn areas(&self, map_id: MapId) -> Vec<Area> {
+        self.areas
+            .read()
+            .unwrap()
+            .get(&map_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn center(area: &Area) -> Position {
+        *area.position()
+            + Position::new(
+                (area.size().width() / 2) as i32,
+                (area.size().height() / 2) as i32,
+            )
+    }
+}
+
+impl DoesDunGen for GridRoomsGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let mut areas = Vec::new();
+        let (cell_width, cell_height) = if self.rows == 0 || self.cols == 0 {
+            (0, 0)
+        } else {
+            (
+                map.size().width() / self.cols,
+                map.size().height() / self.rows,
+            )
+        };
+        if cell_width > 0 && cell_height > 0 {
+            let room_width = cell_width.saturating_sub(self.room_margin * 2).max(1);
+            let room_height = cell_height.saturating_sub(self.room_margin * 2).max(1);
+            for row in 0..self.rows {
+                for col in 0..self.cols {
+                    let position = Position::new(
+                        (col * cell_width + (cell_width - room_width) / 2) as i32,
+                        (row * cell_height + (cell_height - room_height) / 2) as i32,
+                    );
+                    areas.push(Area::new(position, Size::new(room_width, room_height)));
+                }
+            }
+        }
+
+        for area in &areas {
+            for y in 0..area.size().height() as i32 {
+                for x in 0..area.size().width() as i32 {
+                    map.tile_type_at_local_set(
+                        *area.position() + Position::new(x, y),
+                        TileType::Floor,
+                    );
+                }
+            }
+        }
+
+        for (index, area) in areas.iter().enumerate() {
+            let center = Self::center(area);
+            let col = index as u32 % self.cols;
+            let row = index as u32 / self.cols;
+            if col + 1 < self.cols {
+                let east_center = Self::center(&areas[index + 1]);
+                for x in center.x()..=east_center.x() {
+                    map.tile_type_at_local_set(Position::new(x, center.y()), TileType::Floor);
+                }
+            }
+            if row + 1 < self.rows {
+                let south_center = Self::center(&areas[index + self.cols as usize]);
+                for y in center.y()..=south_center.y() {
+                    map.tile_type_at_local_set(Position::new(center.x(), y), TileType::Floor);
+                }
+            }
+        }
+
+        self.areas.write().unwrap().insert(map_id, areas);
+    }
+}
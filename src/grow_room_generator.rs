@@ -0,0 +1,92 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for widening the floor of a map.
+///
+/// For each iteration, the `GrowRoomGenerator` converts every [`TileType`](enum.TileType.html)::Wall or `TileType::Void` tile (or local position with no tile) that is orthogonally adjacent to a `TileType::Floor` tile into `TileType::Floor`. Each iteration works from the tiles as they were at the start of that iteration, so the floor grows by exactly one tile per iteration. Only tiles within the map's [`Size`](geometry/struct.Size.html) are changed, and `TileType::Portal`s are left as-is.
+///
+/// Will create a map 5 tiles wide by 5 tiles high, filled with wall except for a single floor tile in its centre, and grow the floor once into a plus of 5 tiles.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(5, 5), TileType::Wall))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(2, 2), Size::new(1, 1)),
+///         TileType::Floor))
+///     .gen_with(GrowRoomGenerator::new(1))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(map.count_tiles(TileType::Floor) == 5);
+/// for position in &[
+///     Position::new(2, 2),
+///     Position::new(2, 1),
+///     Position::new(3, 2),
+///     Position::new(2, 3),
+///     Position::new(1, 2),
+/// ] {
+///     assert!(map.tile_type_at_local(*position) == Some(TileType::Floor));
+/// }
+/// ```
+pub struct GrowRoomGenerator {
+    iterations: usize,
+}
+
+impl GrowRoomGenerator {
+    /// Creates a new generator for widening the floor of a map by the given number of iterations.
+    pub fn new(iterations: usize) -> Self {
+        Self { iterations }
+    }
+}
+
+impl DoesDunGen for GrowRoomGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        for _ in 0..self.iterations {
+            let mut grown = Vec::new();
+            map.visit_tiles(|position, tile_type| {
+                if tile_type != TileType::Wall && tile_type != TileType::Void {
+                    return;
+                }
+
+                let next_to_floor = [
+                    Position::NORTH,
+                    Position::EAST,
+                    Position::SOUTH,
+                    Position::WEST,
+                ]
+                .iter()
+                .any(|direction| {
+                    map.tile_type_at_local(position + *direction) == Some(TileType::Floor)
+                });
+                if next_to_floor {
+                    grown.push(position);
+                }
+            });
+
+            if grown.is_empty() {
+                break;
+            }
+
+            for position in grown {
+                map.tile_type_at_local_set(position, TileType::Floor);
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// Returns `true` if every tile on the edge of the given [`Map`](trait.Map.html) is [`TileType`](enum.TileType.html)::Wall or `TileType::Portal`.
+///
+/// Useful as the check of a [`RetryGenerator`](struct.RetryGenerator.html), to catch carving which has opened a hole in the walls of a map. A map with no area, or with a missing tile on its edge, does not have a sealed border.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .build();
+/// assert!(has_sealed_border(map_id));
+///
+/// // A portal is the only opening in the walls.
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     map.add_portal(Position::new(0, 2), CardinalDirection::East, Position::zero(), map_id);
+/// }
+/// assert!(has_sealed_border(map_id));
+///
+/// // A stray floor tile in the walls.
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     map.tile_type_at_local_set(Position::new(7, 3), TileType::Floor);
+/// }
+/// assert!(!has_sealed_border(map_id));
+/// ```
+pub fn has_sealed_border(map_id: MapId) -> bool {
+    let maps = &MAPS.read();
+    let map = &maps[map_id].read();
+    let width = map.size().width() as i32;
+    let height = map.size().height() as i32;
+    if width == 0 || height == 0 {
+        return false;
+    }
+
+    let is_sealed = |position: Position| {
+        matches!(
+            map.tile_type_at_local(position),
+            Some(TileType::Wall) | Some(TileType::Portal)
+        )
+    };
+
+    for x in 0..width {
+        if !is_sealed(Position::new(x, 0)) || !is_sealed(Position::new(x, height - 1)) {
+            return false;
+        }
+    }
+    for y in 1..(height - 1) {
+        if !is_sealed(Position::new(0, y)) || !is_sealed(Position::new(width - 1, y)) {
+            return false;
+        }
+    }
+
+    true
+}
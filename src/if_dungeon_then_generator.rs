@@ -0,0 +1,87 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+
+/// Used to conditionally execute a dungeon generator, based on the whole dungeon rather than a single map.
+///
+/// The condition is given the `MapId` of the map being generated on, and is expected to inspect the dungeon reachable from it; for instance, by calling [`reachable_maps()`](fn.reachable_maps.html) to count rooms, or measure the fraction of floor. For conditions on a single map, see [`IfMapThenGenerator`](struct.IfMapThenGenerator.html).
+///
+/// The following chain will generate a map with a [`Size`](geometry/struct.Size.html) of 12 tiles wide by 8 tiles high (including walls) with 2 portals, and then twice try to add 2 more rooms, but only while the dungeon has fewer than 5 rooms. As the first pass brings the dungeon to 5 rooms, the second pass adds nothing.
+///```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 8)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(2, Box::new(|| SparseMap::new())))
+///     .build();
+///
+/// for _ in 0..2 {
+///     DunGen::new(map_id)
+///     .gen_with(IfDungeonThenGenerator::new(
+///         |map_id| reachable_maps(map_id).len() < 5,
+///         EdgePortalsGenerator::new(2, Box::new(|| SparseMap::new())),
+///     ))
+///     .gen_with(TraversePortalsGenerator::new(IfMapThenGenerator::new(
+///         |map_id| *MAPS.read()[map_id].read().size() == Size::zero(),
+///         SequentialGenerator::new(&[
+///             &EmptyRoomGenerator::new(Size::new(8, 6)),
+///             &WalledRoomGenerator::new(Size::zero()),
+///         ]),
+///     )));
+/// }
+///
+/// assert!(reachable_maps(map_id).len() == 5);
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// assert!(map.portal_count() == 4);
+/// for portal in map.portals() {
+///     let target_map = maps[portal.target()].read();
+///     assert!(*target_map.size() == Size::new(8, 6));
+/// }
+///```
+pub struct IfDungeonThenGenerator<TDunGen, TDungeonFunc>
+where
+    TDunGen: DoesDunGen,
+    TDungeonFunc: Fn(MapId) -> bool,
+{
+    dungeon_func: TDungeonFunc,
+    dun_gen: TDunGen,
+}
+
+impl<TDunGen, TDungeonFunc> IfDungeonThenGenerator<TDunGen, TDungeonFunc>
+where
+    TDunGen: DoesDunGen,
+    TDungeonFunc: Fn(MapId) -> bool,
+{
+    /// Creates a new conditional dungeon generator.
+    pub fn new(dungeon_func: TDungeonFunc, dun_gen: TDunGen) -> Self {
+        Self {
+            dungeon_func,
+            dun_gen,
+        }
+    }
+}
+
+impl<TDunGen, TDungeonFunc> DoesDunGen for IfDungeonThenGenerator<TDunGen, TDungeonFunc>
+where
+    TDunGen: DoesDunGen,
+    TDungeonFunc: Fn(MapId) -> bool,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        if (self.dungeon_func)(map_id) {
+            self.dun_gen.dun_gen(target);
+        }
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        if (self.dungeon_func)(map_id) {
+            self.dun_gen.dun_gen_map(map_id);
+        }
+    }
+}
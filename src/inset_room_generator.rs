@@ -0,0 +1,86 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for walling in a map with walls of a given thickness.
+///
+/// The `InsetRoomGenerator` fills the outer rings of the map's [`Size`](geometry/struct.Size.html), to the given thickness, with [`TileType`](enum.TileType.html)::Wall, and fills the interior with `TileType::Floor`. If the thickness leaves no interior, the whole map becomes `TileType::Wall`. Like [`WalledRoomGenerator`](struct.WalledRoomGenerator.html), it will not replace `TileType::Portal`s.
+///
+/// Will generate a map 10 tiles wide, and 10 tiles high, with walls 2 tiles thick; its internal area will consist of `TileType::Floor` and be 6 tiles wide, and 6 tiles high.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(10, 10)))
+///     .gen_with(InsetRoomGenerator::new(2))
+///     .build();
+///
+/// {
+///     let maps = MAPS.read();
+///     let map = maps[map_id].read();
+///
+///     assert!(*map.size() == Size::new(10, 10));
+///     for y in 0..10 {
+///         for x in 0..10 {
+///             let local_position = Position::new(x, y);
+///             if x < 2 || y < 2 || x >= 8 || y >= 8 {
+///                 assert!(map.tile_type_at_local(local_position) == Some(TileType::Wall));
+///             } else {
+///                 assert!(map.tile_type_at_local(local_position) == Some(TileType::Floor));
+///             }
+///         }
+///     }
+///     assert!(map.count_tiles(TileType::Floor) == 6 * 6);
+///     assert!(map.count_tiles(TileType::Wall) == (10 * 10) - (6 * 6));
+/// }
+///
+/// // Walls 5 tiles thick leave no interior.
+/// InsetRoomGenerator::new(5).dun_gen_map(map_id);
+/// assert!(MAPS.read()[map_id].read().count_tiles(TileType::Wall) == 10 * 10);
+/// ```
+pub struct InsetRoomGenerator {
+    thickness: u32,
+}
+
+impl InsetRoomGenerator {
+    /// Creates a new generator for walling in a map with walls of the given thickness.
+    pub fn new(thickness: u32) -> Self {
+        Self { thickness }
+    }
+}
+
+impl DoesDunGen for InsetRoomGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let width = map.size().width();
+        let height = map.size().height();
+        for y in 0..height {
+            for x in 0..width {
+                let position = Position::new(x as i32, y as i32);
+                if map.tile_type_at_local(position) == Some(TileType::Portal) {
+                    continue;
+                }
+
+                let edge_distance = x.min(y).min(width - 1 - x).min(height - 1 - y);
+                let tile_type = if edge_distance < self.thickness {
+                    TileType::Wall
+                } else {
+                    TileType::Floor
+                };
+                map.tile_type_at_local_set(position, tile_type);
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for swapping the floors and walls of a map.
+///
+/// The `InvertTilesGenerator` turns every [`TileType`](enum.TileType.html)::Floor tile into `TileType::Wall`, and every `TileType::Wall` tile into `TileType::Floor`, within the map's [`Size`](geometry/struct.Size.html). `TileType::Portal` and `TileType::Void` tiles are left as-is. This turns a layout where the rooms are floor into its negative, where the rooms are solid.
+///
+/// Will create a walled room 8 tiles wide by 6 tiles high, and invert it.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(InvertTilesGenerator::new())
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(map.count_tiles(TileType::Floor) == (8 * 2) + ((6 * 2) - 4));
+/// assert!(map.count_tiles(TileType::Wall) == (6 * 4));
+/// for y in 0..6 {
+///     for x in 0..8 {
+///         let on_perimeter = x == 0 || y == 0 || x == 7 || y == 5;
+///         let expected = if on_perimeter { TileType::Floor } else { TileType::Wall };
+///         assert!(map.tile_type_at_local(Position::new(x, y)) == Some(expected));
+///     }
+/// }
+/// ```
+pub struct InvertTilesGenerator {}
+
+impl InvertTilesGenerator {
+    /// Creates a new generator for swapping the floors and walls of a map.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl DoesDunGen for InvertTilesGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        for y in 0..map.size().height() {
+            for x in 0..map.size().width() {
+                let position = Position::new(x as i32, y as i32);
+                let inverted_tile_type = match map.tile_type_at_local(position) {
+                    Some(TileType::Floor) => TileType::Wall,
+                    Some(TileType::Wall) => TileType::Floor,
+                    _ => continue,
+                };
+                map.tile_type_at_local_set(position, inverted_tile_type);
+            }
+        }
+    }
+}
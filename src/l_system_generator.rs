@@ -0,0 +1,152 @@
+// External includes.
+
+// Standard includes.
+use std::collections::HashMap;
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for carving branching tunnels into a map with a turtle-graphics L-system.
+///
+/// The `LSystemGenerator` expands the axiom by replacing each character which has a rule with the rule's string, once for each iteration. The expanded string is then followed by a turtle which carves [`TileType`](enum.TileType.html)::Floor:
+///
+/// - `F` moves the turtle forward by the step, carving every tile it passes over.
+/// - `+` turns the turtle 90 degrees to the right, and `-` 90 degrees to the left.
+/// - `[` saves the turtle's position and facing, and `]` restores the last saved position and facing.
+///
+/// All other characters are ignored. The turtle carves its starting tile, and only carves tiles within the map's [`Size`](geometry/struct.Size.html), so the map should be sized beforehand; for example, by filling it with `TileType::Wall`. By default, the turtle starts in the middle of the map, facing north; a specific start can be provided with [`LSystemGenerator::with_start()`](#method.with_start).
+///
+/// Will fill a map 41 tiles wide by 41 tiles high with walls, and carve a single branch of the rule `F` -> `F[+F][-F]` into it.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// use std::collections::HashMap;
+///
+/// let mut rules = HashMap::new();
+/// rules.insert('F', "F[+F][-F]".to_string());
+///
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(41, 41), TileType::Wall))
+///     .gen_with(LSystemGenerator::new("F", rules.clone(), 1, 3))
+///     .build();
+///
+/// {
+///     let maps = MAPS.read();
+///     let map = maps[map_id].read();
+///
+///     // The start, the trunk, and the two branches.
+///     assert!(map.count_tiles(TileType::Floor) == 1 + 3 + 3 + 3);
+///     assert!(map.tile_type_at_local(Position::new(20, 20)) == Some(TileType::Floor));
+///     assert!(map.tile_type_at_local(Position::new(20, 17)) == Some(TileType::Floor));
+///     assert!(map.tile_type_at_local(Position::new(23, 17)) == Some(TileType::Floor));
+///     assert!(map.tile_type_at_local(Position::new(17, 17)) == Some(TileType::Floor));
+/// }
+///
+/// // More iterations grow the tunnels past the edges of the map, but only tiles within the map
+/// // are carved.
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(41, 41), TileType::Wall))
+///     .gen_with(
+///         LSystemGenerator::new("F", rules, 4, 3)
+///             .with_start(Position::new(20, 40), CardinalDirection::North))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// assert!(*map.size() == Size::new(41, 41));
+/// assert!(map.count_tiles(TileType::Floor) > 10);
+/// assert!(map.count_tiles(TileType::Floor) + map.count_tiles(TileType::Wall) == 41 * 41);
+/// ```
+pub struct LSystemGenerator {
+    commands: String,
+    step: u32,
+    start: Option<(Position, CardinalDirection)>,
+}
+
+impl LSystemGenerator {
+    /// Creates a new generator for carving the given L-system into a map.
+    pub fn new(axiom: &str, rules: HashMap<char, String>, iterations: usize, step: u32) -> Self {
+        let mut commands = axiom.to_string();
+        for _ in 0..iterations {
+            let mut expanded = String::new();
+            for command in commands.chars() {
+                match rules.get(&command) {
+                    Some(rule) => expanded.push_str(rule),
+                    None => expanded.push(command),
+                }
+            }
+            commands = expanded;
+        }
+
+        Self {
+            commands,
+            step,
+            start: None,
+        }
+    }
+
+    /// Sets the local position and facing that the turtle starts at.
+    pub fn with_start(mut self, position: Position, facing: CardinalDirection) -> Self {
+        self.start = Some((position, facing));
+        self
+    }
+}
+
+impl DoesDunGen for LSystemGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+        let size = *map.size();
+        let carve = |map: &mut Box<dyn Map>, position: Position| {
+            if position.x() >= 0
+                && position.y() >= 0
+                && (position.x() as u32) < size.width()
+                && (position.y() as u32) < size.height()
+            {
+                map.tile_type_at_local_set(position, TileType::Floor);
+            }
+        };
+
+        let (mut position, mut facing) = self.start.unwrap_or((
+            Position::new((size.width() / 2) as i32, (size.height() / 2) as i32),
+            CardinalDirection::North,
+        ));
+        let mut saved = Vec::new();
+        carve(map, position);
+
+        for command in self.commands.chars() {
+            match command {
+                'F' => {
+                    let offset = match facing {
+                        CardinalDirection::North => Position::NORTH,
+                        CardinalDirection::East => Position::EAST,
+                        CardinalDirection::South => Position::SOUTH,
+                        CardinalDirection::West => Position::WEST,
+                    };
+                    for _ in 0..self.step {
+                        position += offset;
+                        carve(map, position);
+                    }
+                }
+                '+' => facing += CardinalRotation::Right90,
+                '-' => facing += CardinalRotation::Left90,
+                '[' => saved.push((position, facing)),
+                ']' => {
+                    if let Some((saved_position, saved_facing)) = saved.pop() {
+                        position = saved_position;
+                        facing = saved_facing;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::map_ext::TILE_ATTRS;
+
+/// A generator for giving each connected region of floor its own id.
+///
+/// The `LabelRegionsGenerator` finds each group of connected [`TileType`](enum.TileType.html)::Floor tiles on the map, using the given [`Connectivity`](enum.Connectivity.html), and numbers the groups from 0, in row-major order of their first tile. Each floor tile's region id is stored as the `"region"` tile attribute, and can be read back with [`MapExt::get_attr()`](trait.MapExt.html#method.get_attr). Any region ids from an earlier run are removed first, so tiles which are no longer floor have no region.
+///
+/// Will create a map with three separate rooms, and label them.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let rooms = [
+///     Area::new(Position::new(1, 1), Size::new(3, 3)),
+///     Area::new(Position::new(6, 1), Size::new(4, 2)),
+///     Area::new(Position::new(2, 6), Size::new(6, 2)),
+/// ];
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(12, 10), TileType::Wall))
+///     .gen_with(FillTilesGenerator::new(rooms[0], TileType::Floor))
+///     .gen_with(FillTilesGenerator::new(rooms[1], TileType::Floor))
+///     .gen_with(FillTilesGenerator::new(rooms[2], TileType::Floor))
+///     .gen_with(LabelRegionsGenerator::new(Connectivity::Four))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// let mut region_ids = Vec::new();
+/// for room in rooms.iter() {
+///     let region_id = map.get_attr(*room.position(), "region").unwrap();
+///     for y in room.top()..=room.bottom() {
+///         for x in room.left()..=room.right() {
+///             assert!(map.get_attr(Position::new(x, y), "region") == Some(region_id));
+///         }
+///     }
+///     region_ids.push(region_id);
+/// }
+/// assert!(region_ids == vec![0, 1, 2]);
+/// assert!(map.get_attr(Position::new(0, 0), "region") == None);
+/// ```
+pub struct LabelRegionsGenerator {
+    connectivity: Connectivity,
+}
+
+impl LabelRegionsGenerator {
+    /// Creates a new generator for labelling the regions of floor connected by the given `Connectivity`.
+    pub fn new(connectivity: Connectivity) -> Self {
+        Self { connectivity }
+    }
+}
+
+impl DoesDunGen for LabelRegionsGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        if let Some(attrs) = TILE_ATTRS.write().unwrap().get_mut(&map_id) {
+            attrs.retain(|(_, key), _| key != "region");
+        }
+
+        let components = map.floor_connected_components(self.connectivity);
+        for (region_id, component) in components.iter().enumerate() {
+            for position in component {
+                map.set_attr(*position, "region", region_id as i64);
+            }
+        }
+    }
+}
@@ -51,31 +51,167 @@ pub use dungen_minion_rooms::*;
 // Standard includes.
 
 // Internal includes.
+mod area_ext;
+mod choose_generator;
+mod circle_shape;
+mod classify_walls_generator;
+mod clone_map;
+mod composite_shape;
+mod compute_fov;
+mod connectivity;
+mod constrained_size_range;
+mod count_fn;
+mod current_portal_facing;
+mod dead_end_generator;
+mod default_tile_map;
+mod depth_limited_traverse_generator;
+mod directional_size_provider;
+mod distance_field_generator;
 mod dun_gen;
+mod dun_gen_rng;
+mod dun_gen_stream;
+mod dungeon_view;
 mod edge_portals_generator;
+mod edge_positions;
 mod empty_room_generator;
+mod empty_room_mode;
+mod ensure_connected_generator;
+mod erode_room_generator;
+mod export_dot;
+mod farthest_floor_generator;
 mod fill_tiles_generator;
+mod filter_portals_generator;
+mod fractional_area;
+mod free_map;
+mod grid_rooms_generator;
+mod grow_room_generator;
+mod has_sealed_border;
+mod if_dungeon_then_generator;
 mod if_map_then_generator;
+mod inset_room_generator;
+mod invert_tiles_generator;
+mod l_system_generator;
+mod label_regions_generator;
+mod map_ext;
+mod merge_maps_generator;
 mod merge_portal_maps_as_sub_maps_generator;
+mod noise_floor_generator;
+mod openness_loot_generator;
+mod overlap_rooms_generator;
+mod pillars_generator;
+mod portal_ext;
+mod profiling_generator;
+mod reachable_maps;
 mod reciprocate_portals_generator;
+mod recursive_traverse_generator;
+mod remove_isolated_floors_generator;
+mod repair_portals_generator;
+mod replace_tiles_generator;
+mod reroute_portals_generator;
+mod retry_generator;
+mod room_builder;
+mod room_graph;
+mod scale_room_generator;
+mod secret_passage_generator;
 mod sequential_generator;
+mod shape_ext;
+mod solution_maze_generator;
+mod sparse_map_ext;
+mod spiral_room_generator;
 mod sub_map_generator;
+mod symmetry_generator;
+mod thick_corridor_generator;
+mod tile_sender_map;
+mod tile_type_ext;
+mod tiles;
+mod town_generator;
 mod traverse_portals_generator;
 mod traverse_this_and_portals_generator;
+mod trim_to_content_generator;
+mod validate_portal_reciprocity;
 mod walled_room_generator;
+mod weighted_count;
+mod weighted_tile_generator;
+mod wrap_edge_portals_generator;
 
+pub use area_ext::AreaExt;
+pub use choose_generator::ChooseGenerator;
+pub use circle_shape::CircleShape;
+pub use classify_walls_generator::{ClassifyWallsGenerator, WallKind};
+pub use clone_map::clone_map;
+pub use composite_shape::{CompositeShape, ShapeOp};
+pub use compute_fov::compute_fov;
+pub use connectivity::Connectivity;
+pub use constrained_size_range::ConstrainedSizeRange;
+pub use count_fn::CountFn;
+pub use current_portal_facing::current_portal_facing;
+pub use dead_end_generator::DeadEndGenerator;
+pub use depth_limited_traverse_generator::DepthLimitedTraverseGenerator;
+pub use directional_size_provider::DirectionalSizeProvider;
+pub use distance_field_generator::DistanceFieldGenerator;
 pub use dun_gen::DunGen;
+pub use dun_gen_stream::DunGenStream;
+pub use dungeon_view::DungeonView;
 pub use edge_portals_generator::EdgePortalsGenerator;
+pub use edge_positions::EdgePositions;
 pub use empty_room_generator::EmptyRoomGenerator;
+pub use empty_room_mode::EmptyRoomMode;
+pub use ensure_connected_generator::EnsureConnectedGenerator;
+pub use erode_room_generator::ErodeRoomGenerator;
+pub use export_dot::export_dot;
+pub use farthest_floor_generator::FarthestFloorGenerator;
 pub use fill_tiles_generator::FillTilesGenerator;
+pub use filter_portals_generator::FilterPortalsGenerator;
+pub use fractional_area::{provide_placed_shape_within, FractionalArea};
+pub use free_map::{free_map, maps_in_use};
+pub use grid_rooms_generator::GridRoomsGenerator;
+pub use grow_room_generator::GrowRoomGenerator;
+pub use has_sealed_border::has_sealed_border;
+pub use if_dungeon_then_generator::IfDungeonThenGenerator;
 pub use if_map_then_generator::IfMapThenGenerator;
+pub use inset_room_generator::InsetRoomGenerator;
+pub use invert_tiles_generator::InvertTilesGenerator;
+pub use l_system_generator::LSystemGenerator;
+pub use label_regions_generator::LabelRegionsGenerator;
+pub use map_ext::MapExt;
+pub use merge_maps_generator::{MergeMapsGenerator, MergePolicy};
 pub use merge_portal_maps_as_sub_maps_generator::MergePortalMapsAsSubMapsGenerator;
+pub use noise_floor_generator::NoiseFloorGenerator;
+pub use openness_loot_generator::OpennessLootGenerator;
+pub use overlap_rooms_generator::OverlapRoomsGenerator;
+pub use pillars_generator::PillarsGenerator;
+pub use portal_ext::PortalExt;
+pub use profiling_generator::ProfilingGenerator;
+pub use reachable_maps::reachable_maps;
 pub use reciprocate_portals_generator::ReciprocatePortalsGenerator;
+pub use recursive_traverse_generator::RecursiveTraverseGenerator;
+pub use remove_isolated_floors_generator::RemoveIsolatedFloorsGenerator;
+pub use repair_portals_generator::RepairPortalsGenerator;
+pub use replace_tiles_generator::ReplaceTilesGenerator;
+pub use reroute_portals_generator::ReroutePortalsGenerator;
+pub use retry_generator::RetryGenerator;
+pub use room_builder::RoomBuilder;
+pub use room_graph::{build_room_graph, RoomGraph};
+pub use scale_room_generator::ScaleRoomGenerator;
+pub use secret_passage_generator::SecretPassageGenerator;
 pub use sequential_generator::SequentialGenerator;
+pub use solution_maze_generator::SolutionMazeGenerator;
+pub use sparse_map_ext::SparseMapExt;
+pub use spiral_room_generator::SpiralRoomGenerator;
 pub use sub_map_generator::{SubMapGenerator, SubMapGeneratorSet};
+pub use symmetry_generator::{MirrorAxis, SymmetryGenerator};
+pub use thick_corridor_generator::ThickCorridorGenerator;
+pub use tile_type_ext::TileTypeExt;
+pub use tiles::Tiles;
+pub use town_generator::TownGenerator;
 pub use traverse_portals_generator::TraversePortalsGenerator;
 pub use traverse_this_and_portals_generator::TraverseThisAndPortalsGenerator;
+pub use trim_to_content_generator::TrimToContentGenerator;
+pub use validate_portal_reciprocity::{validate_portal_reciprocity, PortalMismatch};
 pub use walled_room_generator::WalledRoomGenerator;
+pub use weighted_count::WeightedCount;
+pub use weighted_tile_generator::WeightedTileGenerator;
+pub use wrap_edge_portals_generator::WrapEdgePortalsGenerator;
 
 #[cfg(test)]
 mod tests {
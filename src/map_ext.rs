@@ -0,0 +1,848 @@
+// External includes.
+use lazy_static::lazy_static;
+
+// Standard includes.
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::RwLock;
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+use crate::portal_ext::PORTAL_LABELS;
+
+/// The attributes of a map's tiles, keyed by local `Position` and key.
+type TileAttrs = HashMap<(Position, String), i64>;
+
+lazy_static! {
+    /// Tile attributes, keyed by the map's `MapId`.
+    pub(crate) static ref TILE_ATTRS: RwLock<HashMap<MapId, TileAttrs>> =
+        RwLock::new(HashMap::new());
+
+    /// Local positions masked out of a map's bounds, keyed by the map's `MapId`.
+    pub(crate) static ref OUT_OF_BOUNDS: RwLock<HashMap<MapId, HashSet<Position>>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Helper methods for querying and building a [`Map`](trait.Map.html); implemented for every type that implements `Map`.
+///
+/// The methods here only rely on the methods provided by `Map`, and so work on any `Map` implementation.
+pub trait MapExt: Map {
+    /// Returns the [`TileType`](enum.TileType.html) at the given local [`Position`](geometry/struct.Position.html), or `TileType::Void` if there is no tile there; for code which treats unset tiles, and positions outside of the map, as empty.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id = SparseMap::new();
+    ///
+    /// let maps = MAPS.read();
+    /// let mut map = maps[map_id].write();
+    /// map.tile_type_at_local_set(Position::new(2, 2), TileType::Floor);
+    ///
+    /// assert!(map.tile_type_at_local_or_void(Position::new(2, 2)) == TileType::Floor);
+    /// // Unset, but within the map's size.
+    /// assert!(map.tile_type_at_local(Position::new(1, 1)) == None);
+    /// assert!(map.tile_type_at_local_or_void(Position::new(1, 1)) == TileType::Void);
+    /// // Outside of the map's size.
+    /// assert!(map.tile_type_at_local(Position::new(5, 5)) == None);
+    /// assert!(map.tile_type_at_local_or_void(Position::new(5, 5)) == TileType::Void);
+    /// ```
+    fn tile_type_at_local_or_void(&self, pos: Position) -> TileType {
+        self.tile_type_at_local(pos).unwrap_or(TileType::Void)
+    }
+
+    /// Returns the number of tiles of the given [`TileType`](enum.TileType.html) within the map's [`Size`](geometry/struct.Size.html).
+    ///
+    /// Local positions which have no tile (`None`) are not counted toward any `TileType`.
+    ///
+    /// A walled map 8 tiles wide by 6 tiles high has 24 `TileType::Floor` tiles and 24 `TileType::Wall` tiles.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+    ///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    ///
+    /// // Area of the inner rectangle.
+    /// assert!(map.count_tiles(TileType::Floor) == (6 * 4));
+    /// // Perimeter of a tiled rectangle.
+    /// assert!(map.count_tiles(TileType::Wall) == ((8 * 2) + ((6 * 2) - 4)));
+    /// assert!(map.count_tiles(TileType::Portal) == 0);
+    /// assert!(map.count_tiles(TileType::Void) == 0);
+    /// ```
+    fn count_tiles(&self, tile_type: TileType) -> usize {
+        let mut count = 0;
+        for y in 0..self.size().height() {
+            for x in 0..self.size().width() {
+                let local_position = Position::new(x as i32, y as i32);
+                if self.tile_type_at_local(local_position) == Some(tile_type) {
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Returns the smallest local [`Area`](geometry/struct.Area.html) enclosing every tile that is not `TileType::Void`, or `None` if the map has no such tiles.
+    ///
+    /// Useful for cropping maps with large empty margins.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id = SparseMap::new();
+    ///
+    /// {
+    ///     let maps = MAPS.read();
+    ///     let mut map = maps[map_id].write();
+    ///     map.tile_type_at_local_set(Position::new(3, 3), TileType::Void);
+    ///     // A map with only `TileType::Void` has no content.
+    ///     assert!(map.floor_bounding_box() == None);
+    ///
+    ///     map.tile_type_at_local_set(Position::new(5, 5), TileType::Floor);
+    ///     assert!(
+    ///         map.floor_bounding_box() == Some(Area::new(Position::new(5, 5), Size::new(1, 1))));
+    ///
+    ///     map.tile_type_at_local_set(Position::new(7, 4), TileType::Wall);
+    ///     assert!(
+    ///         map.floor_bounding_box() == Some(Area::new(Position::new(5, 4), Size::new(3, 2))));
+    /// }
+    /// ```
+    fn floor_bounding_box(&self) -> Option<Area> {
+        let mut bounds: Option<(Position, Position)> = None;
+        for y in 0..self.size().height() {
+            for x in 0..self.size().width() {
+                let local_position = Position::new(x as i32, y as i32);
+                match self.tile_type_at_local(local_position) {
+                    None | Some(TileType::Void) => continue,
+                    Some(_) => {}
+                }
+
+                bounds = Some(match bounds {
+                    None => (local_position, local_position),
+                    Some((min, max)) => (
+                        Position::new(min.x().min(x as i32), min.y().min(y as i32)),
+                        Position::new(max.x().max(x as i32), max.y().max(y as i32)),
+                    ),
+                });
+            }
+        }
+
+        bounds.map(|(min, max)| {
+            Area::new(
+                min,
+                Size::new(
+                    (max.x() - min.x() + 1) as u32,
+                    (max.y() - min.y() + 1) as u32,
+                ),
+            )
+        })
+    }
+
+    /// Calls the provided function with the local [`Position`](geometry/struct.Position.html) and [`TileType`](enum.TileType.html) of every tile within the map's [`Size`](geometry/struct.Size.html), in row-major order.
+    ///
+    /// Local positions which have no tile are visited as `TileType::Void`.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+    ///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    ///
+    /// let mut floor_tile_count = 0;
+    /// map.visit_tiles(|_position, tile_type| {
+    ///     if tile_type == TileType::Floor {
+    ///         floor_tile_count += 1;
+    ///     }
+    /// });
+    /// assert!(floor_tile_count == map.count_tiles(TileType::Floor));
+    /// ```
+    fn visit_tiles<TFunc>(&self, mut func: TFunc)
+    where
+        TFunc: FnMut(Position, TileType),
+    {
+        for y in 0..self.size().height() {
+            for x in 0..self.size().width() {
+                let local_position = Position::new(x as i32, y as i32);
+                let tile_type = self.tile_type_at_local_or_void(local_position);
+                func(local_position, tile_type);
+            }
+        }
+    }
+
+    /// Adds a [`Portal`](struct.Portal.html) to the map, as `add_portal` does, and gives it a label which can be read with [`MapExt::portal_label()`](#method.portal_label), or [`PortalExt::label()`](trait.PortalExt.html#tymethod.label).
+    ///
+    /// Will add a locked portal and a boss entrance to a hub map, reciprocate them, and then merge only the unlocked maps into the hub.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 8)))
+    ///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+    ///     .build();
+    ///
+    /// let (locked_map_id, boss_map_id) = (SparseMap::new(), SparseMap::new());
+    /// {
+    ///     let maps = MAPS.read();
+    ///     let mut map = maps[map_id].write();
+    ///     map.add_portal_labeled(
+    ///         Position::new(0, 3), CardinalDirection::East, Position::zero(), locked_map_id, "locked");
+    ///     map.add_portal_labeled(
+    ///         Position::new(11, 3), CardinalDirection::West, Position::zero(), boss_map_id, "boss entrance");
+    /// }
+    ///
+    /// DunGen::new(map_id)
+    ///     .gen_with(TraversePortalsGenerator::new(SequentialGenerator::new(&[
+    ///         &EmptyRoomGenerator::new(Size::new(8, 6)),
+    ///         &WalledRoomGenerator::new(Size::zero()),
+    ///     ])))
+    ///     .gen_with(TraverseThisAndPortalsGenerator::new(ReciprocatePortalsGenerator::new()))
+    ///     .gen_with(MergePortalMapsAsSubMapsGenerator::new(1, |portal| {
+    ///         portal.label(map_id) != Some("locked".to_string())
+    ///     }));
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    /// let labels = map.portals().into_iter().map(|portal| map.portal_label(portal)).collect::<Vec<_>>();
+    /// assert!(labels == vec![Some("locked".to_string()), Some("boss entrance".to_string())]);
+    /// assert!(map.sub_map_count() == 1);
+    /// assert!(map.get_sub_map_at(0).unwrap().value() == boss_map_id);
+    /// ```
+    fn add_portal_labeled(
+        &mut self,
+        local_position: Position,
+        portal_to_map_facing: CardinalDirection,
+        portal_to_map_position: Position,
+        target: MapId,
+        label: &str,
+    ) {
+        self.add_portal(
+            local_position,
+            portal_to_map_facing,
+            portal_to_map_position,
+            target,
+        );
+        self.set_portal_label(local_position, target, label);
+    }
+
+    /// Returns the label of the given [`Portal`](struct.Portal.html) on the map, if it has one.
+    ///
+    /// See [`MapExt::set_portal_label()`](#method.set_portal_label).
+    fn portal_label(&self, portal: &Portal) -> Option<String> {
+        portal.label(self.map_id())
+    }
+
+    /// Sets the label of the [`Portal`](struct.Portal.html) on the map with the given local [`Position`](geometry/struct.Position.html) and target, replacing any previous label.
+    ///
+    /// Labels belong to the map the portal is on, so portals on different maps never share a label, even if they have the same position and target. They are removed by [`free_map()`](fn.free_map.html).
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let hub_map_id = SparseMap::new();
+    /// let (first_map_id, second_map_id) = (SparseMap::new(), SparseMap::new());
+    ///
+    /// let maps = MAPS.read();
+    /// let mut first_map = maps[first_map_id].write();
+    /// let mut second_map = maps[second_map_id].write();
+    /// first_map.add_portal_labeled(
+    ///     Position::new(5, 0), CardinalDirection::South, Position::zero(), hub_map_id, "locked");
+    /// second_map.add_portal(Position::new(5, 0), CardinalDirection::South, Position::zero(), hub_map_id);
+    ///
+    /// let first_portal = first_map.get_portal_at(0).unwrap().clone();
+    /// let second_portal = second_map.get_portal_at(0).unwrap().clone();
+    /// assert!(first_map.portal_label(&first_portal) == Some("locked".to_string()));
+    /// assert!(second_map.portal_label(&second_portal) == None);
+    ///
+    /// second_map.set_portal_label(Position::new(5, 0), hub_map_id, "secret");
+    /// assert!(first_map.portal_label(&first_portal) == Some("locked".to_string()));
+    /// assert!(second_map.portal_label(&second_portal) == Some("secret".to_string()));
+    /// ```
+    fn set_portal_label(&mut self, local_position: Position, target: MapId, label: &str) {
+        PORTAL_LABELS
+            .write()
+            .unwrap()
+            .entry(self.map_id())
+            .or_default()
+            .insert((local_position, target), label.to_string());
+    }
+
+    /// Returns an iterator over the local [`Position`](geometry/struct.Position.html) and `Option<TileType>` of every tile within the map's [`Size`](geometry/struct.Size.html), in row-major order.
+    ///
+    /// Local positions which have no tile are yielded as `None`.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(3, 2)))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    ///
+    /// let positions = map.tiles().map(|(position, _)| position).collect::<Vec<_>>();
+    /// assert!(positions == vec![
+    ///     Position::new(0, 0), Position::new(1, 0), Position::new(2, 0),
+    ///     Position::new(0, 1), Position::new(1, 1), Position::new(2, 1),
+    /// ]);
+    /// assert!(map.tiles().all(|(_, tile_type)| tile_type == Some(TileType::Floor)));
+    /// ```
+    fn tiles(&self) -> Tiles<'_, Self> {
+        Tiles::new(self)
+    }
+
+    /// Returns the local [`Position`](geometry/struct.Position.html) of every `TileType::Floor` tile within the map's [`Size`](geometry/struct.Size.html), in row-major order.
+    ///
+    /// Useful for choosing where to place content. To avoid collecting the positions, filter [`MapExt::tiles()`](#method.tiles) instead.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+    ///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    ///
+    /// let floor_positions = map.floor_positions();
+    /// assert!(floor_positions.len() == 6 * 4);
+    /// assert!(floor_positions[0] == Position::new(1, 1));
+    /// for position in floor_positions {
+    ///     assert!(position.x() > 0 && position.x() < 7);
+    ///     assert!(position.y() > 0 && position.y() < 5);
+    /// }
+    /// ```
+    fn floor_positions(&self) -> Vec<Position> {
+        self.tiles()
+            .filter(|(_, tile_type)| *tile_type == Some(TileType::Floor))
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Returns the orthogonally adjacent local [`Position`](geometry/struct.Position.html)s whose tile can be walked on; that is, `TileType::Floor` or `TileType::Portal`.
+    ///
+    /// The neighbours are returned in the order north, east, south, west. Positions outside of the map's [`Size`](geometry/struct.Size.html) are never returned, so tiles on the edge of the map have fewer neighbours.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(5, 5)))
+    ///     .gen_with(FillTilesGenerator::new(
+    ///         Area::new(Position::new(4, 0), Size::new(1, 5)),
+    ///         TileType::Wall))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    ///
+    /// // A tile in the middle of the floor.
+    /// assert!(map.walkable_neighbors(Position::new(2, 2)) == vec![
+    ///     Position::new(2, 1), Position::new(3, 2), Position::new(2, 3), Position::new(1, 2),
+    /// ]);
+    /// // A tile in the corner of the map.
+    /// assert!(map.walkable_neighbors(Position::new(0, 0)) == vec![
+    ///     Position::new(1, 0), Position::new(0, 1),
+    /// ]);
+    /// // A tile next to the wall.
+    /// assert!(map.walkable_neighbors(Position::new(3, 2)) == vec![
+    ///     Position::new(3, 1), Position::new(3, 3), Position::new(2, 2),
+    /// ]);
+    /// ```
+    fn walkable_neighbors(&self, position: Position) -> Vec<Position> {
+        [
+            Position::NORTH,
+            Position::EAST,
+            Position::SOUTH,
+            Position::WEST,
+        ]
+        .iter()
+        .map(|direction| position + *direction)
+        .filter(|neighbour| {
+            self.is_local_position_valid(*neighbour)
+                && matches!(
+                    self.tile_type_at_local(*neighbour),
+                    Some(tile_type) if tile_type.is_walkable()
+                )
+        })
+        .collect()
+    }
+
+    /// Returns the groups of connected `TileType::Floor` tiles within the map's [`Size`](geometry/struct.Size.html), with the given [`Connectivity`](enum.Connectivity.html).
+    ///
+    /// The groups are returned in row-major order of their first tile, and each group starts with that tile.
+    ///
+    /// Two blocks of floor which only touch at a corner are one group with `Connectivity::Eight`, but two groups with `Connectivity::Four`.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(FillTilesGenerator::new(Size::new(6, 6), TileType::Wall))
+    ///     .gen_with(FillTilesGenerator::new(
+    ///         Area::new(Position::new(0, 0), Size::new(3, 3)),
+    ///         TileType::Floor))
+    ///     .gen_with(FillTilesGenerator::new(
+    ///         Area::new(Position::new(3, 3), Size::new(3, 3)),
+    ///         TileType::Floor))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    ///
+    /// let components = map.floor_connected_components(Connectivity::Four);
+    /// assert!(components.len() == 2);
+    /// assert!(components[0].len() == 9 && components[1].len() == 9);
+    /// assert!(components[0][0] == Position::new(0, 0));
+    ///
+    /// let components = map.floor_connected_components(Connectivity::Eight);
+    /// assert!(components.len() == 1);
+    /// assert!(components[0].len() == 18);
+    /// ```
+    fn floor_connected_components(&self, connectivity: Connectivity) -> Vec<Vec<Position>> {
+        let neighbor_offsets = connectivity.neighbor_offsets();
+        let mut visited = HashSet::new();
+        let mut components = Vec::new();
+        self.visit_tiles(|position, tile_type| {
+            if tile_type != TileType::Floor || !visited.insert(position) {
+                return;
+            }
+
+            let mut component = vec![position];
+            let mut open = VecDeque::new();
+            open.push_back(position);
+            while let Some(position) = open.pop_front() {
+                for offset in &neighbor_offsets {
+                    let neighbour = position + *offset;
+                    if self.is_local_position_valid(neighbour)
+                        && self.tile_type_at_local(neighbour) == Some(TileType::Floor)
+                        && visited.insert(neighbour)
+                    {
+                        component.push(neighbour);
+                        open.push_back(neighbour);
+                    }
+                }
+            }
+
+            components.push(component);
+        });
+
+        components
+    }
+
+    /// Returns the [`Portal`](struct.Portal.html) at the given local [`Position`](geometry/struct.Position.html), or `None` if there is no portal there.
+    ///
+    /// If several portals share the position, the first one added is returned.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+    ///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+    ///     .build();
+    ///
+    /// let portals = [
+    ///     (Position::new(3, 0), CardinalDirection::South, SparseMap::new()),
+    ///     (Position::new(0, 2), CardinalDirection::East, SparseMap::new()),
+    ///     (Position::new(7, 4), CardinalDirection::West, SparseMap::new()),
+    /// ];
+    ///
+    /// let maps = MAPS.read();
+    /// let mut map = maps[map_id].write();
+    /// for (local_position, facing, target) in &portals {
+    ///     map.add_portal(*local_position, *facing, Position::zero(), *target);
+    /// }
+    ///
+    /// for (local_position, facing, target) in &portals {
+    ///     let portal = map.portal_at(*local_position).unwrap();
+    ///     assert!(portal.target() == *target);
+    ///     assert!(*portal.portal_to_map_facing() == *facing);
+    /// }
+    /// assert!(map.portal_at(Position::new(3, 3)).is_none());
+    /// ```
+    fn portal_at(&self, local_position: Position) -> Option<&Portal> {
+        self.portals()
+            .into_iter()
+            .find(|portal| *portal.local_position() == local_position)
+    }
+
+    /// Changes the [`Size`](geometry/struct.Size.html) of the map, keeping the tiles which are within both the old and the new size.
+    ///
+    /// Tiles outside of the new size are set to `TileType::Void`, and any new space is left without tiles, and their attributes, and bounds mask, are dropped. The map's position is unchanged. A `Map` has no way to remove its [`Portal`](struct.Portal.html)s and [`SubMap`](struct.SubMap.html)s, so they are left where they are, and may end up outside of the new size; as they have not moved, the portals of other maps which lead back to them still match.
+    ///
+    /// Will create a floored map 10 tiles wide by 10 tiles high, and shrink it to 5 tiles wide by 5 tiles high.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(10, 10)))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let mut map = maps[map_id].write();
+    /// map.resize(Size::new(5, 5));
+    ///
+    /// assert!(*map.size() == Size::new(5, 5));
+    /// assert!(map.count_tiles(TileType::Floor) == 25);
+    /// assert!(map.tile_type_at_local(Position::new(4, 4)) == Some(TileType::Floor));
+    /// assert!(map.tile_type_at_local(Position::new(5, 5)) != Some(TileType::Floor));
+    ///
+    /// // Growing the map again does not bring back the dropped tiles.
+    /// map.resize(Size::new(10, 10));
+    /// assert!(map.count_tiles(TileType::Floor) == 25);
+    /// ```
+    ///
+    /// Will shrink a map with a portal, a sub-map, and a tile attribute outside of its new size; only the attribute is dropped.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(10, 10)))
+    ///     .build();
+    /// let (target_map_id, sub_map_id) = (SparseMap::new(), SparseMap::new());
+    ///
+    /// let maps = MAPS.read();
+    /// let mut map = maps[map_id].write();
+    /// map.add_portal_labeled(
+    ///     Position::new(9, 3), CardinalDirection::West, Position::zero(), target_map_id, "exit");
+    /// map.add_sub_map(Position::new(7, 8), sub_map_id);
+    /// map.set_attr(Position::new(8, 8), "depth", 3);
+    /// map.resize(Size::new(5, 5));
+    ///
+    /// // The portal and sub-map are left in place, outside of the new size.
+    /// assert!(*map.size() == Size::new(5, 5));
+    /// let portal = map.get_portal_at(0).unwrap();
+    /// assert!(*portal.local_position() == Position::new(9, 3));
+    /// assert!(map.portal_label(portal) == Some("exit".to_string()));
+    /// assert!(*map.get_sub_map_at(0).unwrap().local_position() == Position::new(7, 8));
+    /// // The tiles kept by the resize are untouched.
+    /// assert!(map.count_tiles(TileType::Floor) == 25);
+    ///
+    /// // Growing the map again does not bring back the dropped attribute.
+    /// map.resize(Size::new(10, 10));
+    /// assert!(map.get_attr(Position::new(8, 8), "depth") == None);
+    /// ```
+    fn resize(&mut self, new_size: Size) {
+        let old_size = *self.size();
+        for y in 0..old_size.height() {
+            for x in 0..old_size.width() {
+                if x < new_size.width() && y < new_size.height() {
+                    continue;
+                }
+
+                let local_position = Position::new(x as i32, y as i32);
+                if self.tile_type_at_local(local_position).is_some() {
+                    self.tile_type_at_local_set(local_position, TileType::Void);
+                }
+            }
+        }
+
+        *self.size_mut() = new_size;
+
+        let map_id = self.map_id();
+        let is_within = |position: Position| {
+            position.x() >= 0
+                && position.y() >= 0
+                && (position.x() as u32) < new_size.width()
+                && (position.y() as u32) < new_size.height()
+        };
+        if let Some(attrs) = TILE_ATTRS.write().unwrap().get_mut(&map_id) {
+            attrs.retain(|(position, _), _| is_within(*position));
+        }
+        if let Some(positions) = OUT_OF_BOUNDS.write().unwrap().get_mut(&map_id) {
+            positions.retain(|position| is_within(*position));
+        }
+    }
+
+    /// Renders the map's tiles as text, one line per row, with each line ending in a newline.
+    ///
+    /// Each tile is rendered with [`TileTypeExt::to_char()`](trait.TileTypeExt.html#tymethod.to_char), and local positions with no tile are rendered as `TileType::Void`. To choose the characters, use [`MapExt::to_ascii_string_with()`](#method.to_ascii_string_with).
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(4, 3)))
+    ///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    /// assert!(map.to_ascii_string() == "####\n#..#\n####\n");
+    /// ```
+    fn to_ascii_string(&self) -> String {
+        self.to_ascii_string_with(|tile_type| tile_type.unwrap_or(TileType::Void).to_char())
+    }
+
+    /// Renders the map's tiles as text, one line per row, with each line ending in a newline; each tile is rendered as the character returned by the given function.
+    ///
+    /// The function is given `None` for local positions with no tile.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(4, 3)))
+    ///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    /// let text = map.to_ascii_string_with(|tile_type| match tile_type {
+    ///     Some(TileType::Floor) => ',',
+    ///     Some(tile_type) => tile_type.to_char(),
+    ///     None => ' ',
+    /// });
+    /// assert!(text == "####\n#,,#\n####\n");
+    /// ```
+    fn to_ascii_string_with<TFunc>(&self, func: TFunc) -> String
+    where
+        TFunc: Fn(Option<TileType>) -> char,
+    {
+        let mut output = String::new();
+        for y in 0..self.size().height() {
+            for x in 0..self.size().width() {
+                output.push(func(
+                    self.tile_type_at_local(Position::new(x as i32, y as i32)),
+                ));
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Sets the [`TileType`](enum.TileType.html) of each of the given local [`Position`](geometry/struct.Position.html)s, in order, as `tile_type_at_local_set()` does; the map grows to fit the tiles.
+    ///
+    /// Each tile is written in turn, as `tile_type_at_local_set()` does, so this is no faster than a loop; generators which write many tiles, such as [`FillTilesGenerator`](struct.FillTilesGenerator.html), write through it, so that there is one place to speed up if `SparseMap` gains a way to reserve space for tiles.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id = SparseMap::new();
+    ///
+    /// let maps = MAPS.read();
+    /// let mut map = maps[map_id].write();
+    /// map.set_tiles(vec![
+    ///     (Position::new(0, 0), TileType::Wall),
+    ///     (Position::new(1, 0), TileType::Floor),
+    ///     (Position::new(2, 1), TileType::Portal),
+    ///     (Position::new(0, 0), TileType::Floor),
+    /// ]);
+    ///
+    /// assert!(*map.size() == Size::new(3, 2));
+    /// assert!(map.tile_type_at_local(Position::new(0, 0)) == Some(TileType::Floor));
+    /// assert!(map.tile_type_at_local(Position::new(1, 0)) == Some(TileType::Floor));
+    /// assert!(map.tile_type_at_local(Position::new(2, 1)) == Some(TileType::Portal));
+    /// assert!(map.tile_type_at_local(Position::new(0, 1)) == None);
+    /// ```
+    fn set_tiles<TCells>(&mut self, cells: TCells)
+    where
+        TCells: IntoIterator<Item = (Position, TileType)>,
+    {
+        for (position, tile_type) in cells {
+            self.tile_type_at_local_set(position, tile_type);
+        }
+    }
+
+    /// Returns the distance, in orthogonal steps, from every `TileType::Floor` tile to the nearest tile which is not `TileType::Floor`.
+    ///
+    /// Tiles outside of the map's [`Size`](geometry/struct.Size.html) are treated as walls, so floor tiles on the edge of the map have a distance of 1.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(5, 5)))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    ///
+    /// let wall_distances = map.wall_distances();
+    /// assert!(wall_distances.len() == 5 * 5);
+    /// assert!(wall_distances[&Position::new(0, 0)] == 1);
+    /// assert!(wall_distances[&Position::new(1, 2)] == 2);
+    /// assert!(wall_distances[&Position::new(2, 2)] == 3);
+    /// ```
+    fn wall_distances(&self) -> HashMap<Position, u32> {
+        let mut distances = HashMap::new();
+        let mut open = VecDeque::new();
+        let is_floor = |position: Position| {
+            self.is_local_position_valid(position)
+                && self.tile_type_at_local(position) == Some(TileType::Floor)
+        };
+
+        self.visit_tiles(|position, tile_type| {
+            if tile_type != TileType::Floor {
+                return;
+            }
+
+            let next_to_wall = [
+                Position::NORTH,
+                Position::EAST,
+                Position::SOUTH,
+                Position::WEST,
+            ]
+            .iter()
+            .any(|direction| !is_floor(position + *direction));
+            if next_to_wall {
+                distances.insert(position, 1);
+                open.push_back(position);
+            }
+        });
+
+        while let Some(position) = open.pop_front() {
+            let distance = distances[&position];
+            for direction in [
+                Position::NORTH,
+                Position::EAST,
+                Position::SOUTH,
+                Position::WEST,
+            ]
+            .iter()
+            {
+                let neighbour = position + *direction;
+                if is_floor(neighbour) && !distances.contains_key(&neighbour) {
+                    distances.insert(neighbour, distance + 1);
+                    open.push_back(neighbour);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Sets the attribute with the given key, on the tile at the given local [`Position`](geometry/struct.Position.html), to the given value.
+    ///
+    /// Attributes are scratch state attached to a map's tiles, such as visited flags or region ids, for generators which need to remember something about each tile. They are stored by the map's [`MapId`](type.MapId.html), and are removed by [`free_map()`](fn.free_map.html).
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(4, 4)))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let mut map = maps[map_id].write();
+    ///
+    /// map.set_attr(Position::new(1, 1), "region", 3);
+    /// map.set_attr(Position::new(1, 1), "visited", 1);
+    /// map.set_attr(Position::new(2, 3), "region", -7);
+    ///
+    /// assert!(map.get_attr(Position::new(1, 1), "region") == Some(3));
+    /// assert!(map.get_attr(Position::new(1, 1), "visited") == Some(1));
+    /// assert!(map.get_attr(Position::new(2, 3), "region") == Some(-7));
+    /// assert!(map.get_attr(Position::new(2, 3), "visited") == None);
+    /// assert!(map.get_attr(Position::new(0, 0), "region") == None);
+    ///
+    /// map.set_attr(Position::new(1, 1), "region", 4);
+    /// assert!(map.get_attr(Position::new(1, 1), "region") == Some(4));
+    /// ```
+    fn set_attr(&mut self, position: Position, key: &str, value: i64) {
+        TILE_ATTRS
+            .write()
+            .unwrap()
+            .entry(self.map_id())
+            .or_default()
+            .insert((position, key.to_string()), value);
+    }
+
+    /// Returns the value of the attribute with the given key, on the tile at the given local [`Position`](geometry/struct.Position.html), or `None` if it has not been set.
+    ///
+    /// See [`MapExt::set_attr()`](#method.set_attr).
+    fn get_attr(&self, position: Position, key: &str) -> Option<i64> {
+        TILE_ATTRS
+            .read()
+            .unwrap()
+            .get(&self.map_id())
+            .and_then(|attrs| attrs.get(&(position, key.to_string())))
+            .copied()
+    }
+
+    /// Masks the given local [`Position`](geometry/struct.Position.html) out of the map's bounds, or back in, for maps which are not rectangular.
+    ///
+    /// [`FillTilesGenerator`](struct.FillTilesGenerator.html), [`EmptyRoomGenerator`](struct.EmptyRoomGenerator.html), and [`WalledRoomGenerator`](struct.WalledRoomGenerator.html) never write to a masked-out position. The mask is stored by the map's [`MapId`](type.MapId.html), and is removed by [`free_map()`](fn.free_map.html); it does not change the map's [`Size`](geometry/struct.Size.html), or the results of its own methods, such as `contains_position`.
+    ///
+    /// Will mask the top-left quarter out of a map 8 tiles wide by 6 tiles high, fill the rest of it with floor, and wall it in along both the edge of the map and the edge of the mask.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id = SparseMap::new();
+    /// {
+    ///     let maps = MAPS.read();
+    ///     let mut map = maps[map_id].write();
+    ///     for y in 0..3 {
+    ///         for x in 0..4 {
+    ///             map.set_in_bounds(Position::new(x, y), false);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// DunGen::new(map_id)
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+    ///     .gen_with(WalledRoomGenerator::new(Size::zero()));
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    /// for y in 0..3 {
+    ///     for x in 0..4 {
+    ///         assert!(!map.is_in_bounds(Position::new(x, y)));
+    ///         assert!(map.tile_type_at_local(Position::new(x, y)) == None);
+    ///     }
+    /// }
+    /// assert!(map.is_in_bounds(Position::new(4, 0)));
+    /// assert!(map.tile_type_at_local(Position::new(4, 0)) == Some(TileType::Wall));
+    /// // The edge of the mask is walled.
+    /// for y in 0..4 {
+    ///     assert!(map.tile_type_at_local(Position::new(4, y)) == Some(TileType::Wall));
+    /// }
+    /// for x in 0..4 {
+    ///     assert!(map.tile_type_at_local(Position::new(x, 3)) == Some(TileType::Wall));
+    /// }
+    /// assert!(map.tile_type_at_local(Position::new(5, 1)) == Some(TileType::Floor));
+    /// assert!(map.tile_type_at_local(Position::new(1, 4)) == Some(TileType::Floor));
+    /// assert!(map.count_tiles(TileType::Floor) + map.count_tiles(TileType::Wall) == (8 * 6) - (4 * 3));
+    /// // The inner floor is the L-shape left once its walls are taken away.
+    /// assert!(map.count_tiles(TileType::Floor) == (2 * 3) + (6 * 1));
+    /// ```
+    fn set_in_bounds(&mut self, position: Position, in_bounds: bool) {
+        let mut out_of_bounds = OUT_OF_BOUNDS.write().unwrap();
+        if in_bounds {
+            if let Some(positions) = out_of_bounds.get_mut(&self.map_id()) {
+                positions.remove(&position);
+            }
+        } else {
+            out_of_bounds
+                .entry(self.map_id())
+                .or_default()
+                .insert(position);
+        }
+    }
+
+    /// Returns `false` if the given local [`Position`](geometry/struct.Position.html) has been masked out of the map's bounds, and `true` otherwise; the map's [`Size`](geometry/struct.Size.html) is not considered.
+    ///
+    /// See [`MapExt::set_in_bounds()`](#method.set_in_bounds).
+    fn is_in_bounds(&self, position: Position) -> bool {
+        !matches!(
+            OUT_OF_BOUNDS.read().unwrap().get(&self.map_id()),
+            Some(positions) if positions.contains(&position)
+        )
+    }
+}
+
+impl<TMap> MapExt for TMap where TMap: Map + ?Sized {}
@@ -0,0 +1,170 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// Decides which tiles of a map a [`MergeMapsGenerator`](struct.MergeMapsGenerator.html) overwrites.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum MergePolicy {
+    /// Every tile of the source map overwrites the tile beneath it, except for `TileType::Portal`.
+    Always,
+    /// Tiles of the source map are only placed where the map is `TileType::Void`, or has no tile.
+    OnlyVoid,
+    /// As `OnlyVoid`, but `TileType::Floor` tiles of the source map also overwrite `TileType::Wall` tiles, carving through them.
+    FloorOverWall,
+}
+
+/// A generator for stamping the tiles and portals of one map into another.
+///
+/// The `MergeMapsGenerator` copies every tile of the source map which is not [`TileType`](enum.TileType.html)::Void or `TileType::Portal` into the map, moved by the given offset, overwriting the map's tiles according to the given [`MergePolicy`](enum.MergePolicy.html). The map's own `TileType::Portal` tiles are never overwritten, whatever the policy, so that each of its portals keeps its tile. Each [`Portal`](struct.Portal.html) of the source map is also added to the map, along with its `TileType::Portal` tile, moved by the same offset, whatever the policy. The source map is left as-is.
+///
+/// Will create a walled room 4 tiles wide by 3 tiles high, with a portal on its west wall, and stamp it into a floor 12 tiles wide by 8 tiles high.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let room_map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(4, 3)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .build();
+/// let target_map_id = SparseMap::new();
+/// {
+///     let maps = MAPS.read();
+///     let mut room_map = maps[room_map_id].write();
+///     room_map.add_portal(Position::new(0, 1), CardinalDirection::East, Position::zero(), target_map_id);
+/// }
+///
+/// let offset = Position::new(3, 2);
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 8)))
+///     .gen_with(MergeMapsGenerator::new(room_map_id, offset, MergePolicy::Always))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let room_map = maps[room_map_id].read();
+/// let map = maps[map_id].read();
+///
+/// assert!(*map.size() == Size::new(12, 8));
+/// for y in 0..3 {
+///     for x in 0..4 {
+///         let position = Position::new(x, y);
+///         assert!(map.tile_type_at_local(position + offset) == room_map.tile_type_at_local(position));
+///     }
+/// }
+/// assert!(map.count_tiles(TileType::Floor) == (12 * 8) - (4 * 3) + 2);
+///
+/// assert!(map.portal_count() == 1);
+/// let portal = map.portal_at(Position::new(3, 3)).unwrap();
+/// assert!(portal.target() == target_map_id);
+/// assert!(*portal.portal_to_map_facing() == CardinalDirection::East);
+/// ```
+///
+/// Will stamp a floor over a map with a portal; the portal keeps its tile.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let floor_map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(4, 4)))
+///     .build();
+/// let target_map_id = SparseMap::new();
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(6, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .build();
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     map.add_portal(Position::new(2, 2), CardinalDirection::North, Position::zero(), target_map_id);
+/// }
+///
+/// DunGen::new(map_id)
+///     .gen_with(MergeMapsGenerator::new(floor_map_id, Position::new(1, 1), MergePolicy::Always));
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// assert!(map.tile_type_at_local(Position::new(2, 2)) == Some(TileType::Portal));
+/// assert!(map.portal_at(Position::new(2, 2)).unwrap().target() == target_map_id);
+/// assert!(map.count_tiles(TileType::Portal) == map.portal_count());
+/// assert!(map.count_tiles(TileType::Floor) == (4 * 4) - 1);
+/// ```
+pub struct MergeMapsGenerator {
+    source: MapId,
+    offset: Position,
+    policy: MergePolicy,
+}
+
+impl MergeMapsGenerator {
+    /// Creates a new generator for stamping the source map into a map at the given offset.
+    pub fn new(source: MapId, offset: Position, policy: MergePolicy) -> Self {
+        Self {
+            source,
+            offset,
+            policy,
+        }
+    }
+}
+
+impl DoesDunGen for MergeMapsGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        // Copied out first, so that a map can be merged into itself.
+        let mut source_tiles = Vec::new();
+        let mut source_portals = Vec::new();
+        {
+            let maps = &MAPS.read();
+            let source = &maps[self.source].read();
+            source.visit_tiles(|position, tile_type| {
+                if tile_type != TileType::Void && tile_type != TileType::Portal {
+                    source_tiles.push((position, tile_type));
+                }
+            });
+            for portal in source.portals() {
+                source_portals.push((portal.clone(), portal.label(self.source)));
+            }
+        }
+
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        for (position, tile_type) in source_tiles {
+            let position = position + self.offset;
+            let existing = map.tile_type_at_local_or_void(position);
+            // A `TileType::Portal` tile belongs to a `Portal`, and is never overwritten.
+            let overwrite = existing != TileType::Portal
+                && match self.policy {
+                    MergePolicy::Always => true,
+                    MergePolicy::OnlyVoid => existing == TileType::Void,
+                    MergePolicy::FloorOverWall => {
+                        existing == TileType::Void
+                            || (tile_type == TileType::Floor && existing == TileType::Wall)
+                    }
+                };
+            if overwrite {
+                map.tile_type_at_local_set(position, tile_type);
+            }
+        }
+
+        for (portal, label) in source_portals {
+            let local_position = *portal.local_position() + self.offset;
+            map.add_portal(
+                local_position,
+                *portal.portal_to_map_facing(),
+                *portal.portal_to_map_position(),
+                portal.target(),
+            );
+            if let Some(label) = label {
+                map.set_portal_label(local_position, portal.target(), &label);
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// External includes.
+use rand::Rng;
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::dun_gen_rng::dun_gen_rng;
+use crate::geometry::*;
+
+/// A generator for filling a map with varied terrain using value noise.
+///
+/// The `NoiseFloorGenerator` generates a value-noise field over the [`Area`](geometry/struct.Area.html) of the map, with values in the range [0, 1), and sets each tile to [`TileType`](enum.TileType.html)::Floor where the noise exceeds the threshold, and to `TileType::Wall` elsewhere. The scale is the frequency of the noise; smaller scales produce larger features.
+///
+/// By default, a new random seed is used every time the generator is run. A specific seed can be provided with [`NoiseFloorGenerator::with_seed()`](#method.with_seed) to reproduce the same terrain.
+///
+/// Will create a map with a `Size` of 40 tiles wide by 30 tiles high, and fill it with noise at increasing thresholds; higher thresholds produce less floor, and the same seed always produces the same terrain.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let generate = |threshold: f64| {
+///     let map_id =
+///         DunGen::new(SparseMap::new())
+///         .gen_with(EmptyRoomGenerator::new(Size::new(40, 30)))
+///         .gen_with(NoiseFloorGenerator::new(0.2, threshold).with_seed(12345))
+///         .build();
+///
+///     let maps = MAPS.read();
+///     let map = maps[map_id].read();
+///     let mut tiles = Vec::new();
+///     map.visit_tiles(|_position, tile_type| tiles.push(tile_type));
+///     assert!(*map.size() == Size::new(40, 30));
+///     assert!(map.count_tiles(TileType::Floor) + map.count_tiles(TileType::Wall) == 40 * 30);
+///     (map.count_tiles(TileType::Floor), tiles)
+/// };
+///
+/// let mut last_floor_count = usize::MAX;
+/// for threshold in &[0.0, 0.2, 0.4, 0.6, 0.8, 1.0] {
+///     let (floor_count, _) = generate(*threshold);
+///     assert!(floor_count <= last_floor_count);
+///     last_floor_count = floor_count;
+/// }
+/// assert!(last_floor_count == 0);
+///
+/// assert!(generate(0.5).1 == generate(0.5).1);
+/// ```
+pub struct NoiseFloorGenerator {
+    scale: f64,
+    threshold: f64,
+    seed: Option<u64>,
+}
+
+impl NoiseFloorGenerator {
+    /// Creates a new generator for filling a map with value noise, using a random seed.
+    pub fn new(scale: f64, threshold: f64) -> Self {
+        Self {
+            scale,
+            threshold,
+            seed: None,
+        }
+    }
+
+    /// Sets the seed for the noise, so that the same terrain is generated every time.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn lattice_value(seed: u64, x: i64, y: i64) -> f64 {
+        // A SplitMix64-style hash of the seed and the lattice coordinates.
+        let mut hash = seed
+            ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        hash ^= hash >> 31;
+
+        (hash >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn noise(seed: u64, x: f64, y: f64) -> f64 {
+        let (x0, y0) = (x.floor(), y.floor());
+        let smooth = |t: f64| t * t * (3.0 - 2.0 * t);
+        let (tx, ty) = (smooth(x - x0), smooth(y - y0));
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        let top = Self::lattice_value(seed, x0, y0) * (1.0 - tx)
+            + Self::lattice_value(seed, x0 + 1, y0) * tx;
+        let bottom = Self::lattice_value(seed, x0, y0 + 1) * (1.0 - tx)
+            + Self::lattice_value(seed, x0 + 1, y0 + 1) * tx;
+
+        top * (1.0 - ty) + bottom * ty
+    }
+}
+
+impl DoesDunGen for NoiseFloorGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let seed = self.seed.unwrap_or_else(|| dun_gen_rng().gen());
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        for y in 0..map.size().height() {
+            for x in 0..map.size().width() {
+                let position = Position::new(x as i32, y as i32);
+                let value = Self::noise(seed, x as f64 * self.scale, y as f64 * self.scale);
+                let tile_type = if value > self.threshold {
+                    TileType::Floor
+                } else {
+                    TileType::Wall
+                };
+                map.tile_type_at_local_set(position, tile_type);
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+// External includes.
+use rand::distributions::{Distribution, WeightedIndex};
+
+// Standard includes.
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+// Internal includes.
+use super::*;
+use crate::dun_gen_rng::dun_gen_rng;
+use crate::geometry::*;
+
+/// A generator for choosing loot positions on a map, favouring open areas over cramped corridors.
+///
+/// The `OpennessLootGenerator` is called with an explicit count, or an instance of [`ProvidesCount`](geometry/trait.ProvidesCount.html), of loot positions to choose, and a bias exponent. Each [`TileType`](enum.TileType.html)::Floor tile is weighted by its [`wall distance`](trait.MapExt.html#method.wall_distances) raised to the power of the bias exponent; a bias of 0 chooses uniformly, while larger biases increasingly favour the centres of rooms. No position will be chosen more than once.
+///
+/// The `OpennessLootGenerator` does not change any tiles; the chosen positions are stored per [`MapId`](type.MapId.html), and can be retrieved after generation through [`OpennessLootGenerator::loot_positions()`](#method.loot_positions).
+///
+/// Will create a walled room with a `Size` of 24 tiles wide by 12 tiles high, fill most of its right side in to leave a narrow corridor, and then place 5 pieces of loot.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// for _ in 0..20 {
+///     let loot_generator = OpennessLootGenerator::new(5, 4.0);
+///     let map_id =
+///         DunGen::new(SparseMap::new())
+///         .gen_with(EmptyRoomGenerator::new(Size::new(24, 12)))
+///         .gen_with(WalledRoomGenerator::new(Size::zero()))
+///         .gen_with(FillTilesGenerator::new(
+///             Area::new(Position::new(12, 1), Size::new(11, 8)),
+///             TileType::Wall))
+///         .build();
+///     loot_generator.dun_gen_map(map_id);
+///
+///     let maps = MAPS.read();
+///     let map = maps[map_id].read();
+///     let wall_distances = map.wall_distances();
+///
+///     let loot_positions = loot_generator.loot_positions(map_id);
+///     assert!(loot_positions.len() == 5);
+///     let loot_average = loot_positions
+///         .iter()
+///         .map(|position| wall_distances[position] as f64)
+///         .sum::<f64>() / loot_positions.len() as f64;
+///     let map_average = wall_distances
+///         .values()
+///         .map(|distance| *distance as f64)
+///         .sum::<f64>() / wall_distances.len() as f64;
+///     assert!(loot_average > map_average);
+/// }
+/// ```
+pub struct OpennessLootGenerator<TProvidesCount>
+where
+    TProvidesCount: ProvidesCount + Sized,
+{
+    provides_count: TProvidesCount,
+    bias: f64,
+    loot_positions: RwLock<HashMap<MapId, Vec<Position>>>,
+}
+
+impl<TProvidesCount> OpennessLootGenerator<TProvidesCount>
+where
+    TProvidesCount: ProvidesCount + Sized,
+{
+    /// Creates a new generator for choosing loot positions, weighted toward open areas by the given bias exponent.
+    pub fn new(provides_count: TProvidesCount, bias: f64) -> Self {
+        Self {
+            provides_count,
+            bias,
+            loot_positions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the loot positions chosen for the given `MapId`, if any.
+    pub fn loot_positions(&self, map_id: MapId) -> Vec<Position> {
+        self.loot_positions
+            .read()
+            .unwrap()
+            .get(&map_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl<TProvidesCount> DoesDunGen for OpennessLootGenerator<TProvidesCount>
+where
+    TProvidesCount: ProvidesCount + Sized,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let mut candidates = {
+            let maps = &MAPS.read();
+            let map = &maps[map_id].read();
+            map.wall_distances()
+                .into_iter()
+                .map(|(position, distance)| (position, (distance as f64).powf(self.bias)))
+                .collect::<Vec<_>>()
+        };
+        // Sort for a consistent starting order, as `HashMap` iteration order is unspecified.
+        candidates.sort_by_key(|(position, _)| (position.y(), position.x()));
+
+        let count = self.provides_count.provide_count();
+        let mut rng = dun_gen_rng();
+        let mut chosen = Vec::new();
+        for _ in 0..count {
+            let weighted_index =
+                match WeightedIndex::new(candidates.iter().map(|(_, weight)| *weight)) {
+                    Ok(weighted_index) => weighted_index,
+                    // There are no candidates left with a non-zero weight.
+                    Err(_) => break,
+                };
+            let index = weighted_index.sample(&mut rng);
+            chosen.push(candidates.swap_remove(index).0);
+        }
+
+        self.loot_positions.write().unwrap().insert(map_id, chosen);
+    }
+}
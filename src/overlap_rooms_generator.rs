@@ -0,0 +1,168 @@
+// External includes.
+use rand::Rng;
+
+// Standard includes.
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+// Internal includes.
+use super::*;
+use crate::dun_gen_rng::dun_gen_rng;
+use crate::geometry::*;
+
+/// A generator for scattering rectangular rooms across a map, without letting them overlap.
+///
+/// The `OverlapRoomsGenerator` attempts to place a number of rooms, provided by a [`ProvidesCount`](geometry/trait.ProvidesCount.html), with sizes drawn from a [`SizeRange`](geometry/struct.SizeRange.html). Each room is tried at up to `max_tries` random positions within the map's [`Size`](geometry/struct.Size.html); a position is rejected if the room, or the 1 tile around it, would overlap a room which has already been placed. If none of the positions fit, the room is skipped. The placed rooms are carved out as [`TileType`](enum.TileType.html)::Floor, and the rest of the map is left as-is.
+///
+/// The [`Area`](geometry/struct.Area.html)s of the placed rooms are stored per [`MapId`](type.MapId.html), and can be retrieved after generation through [`OverlapRoomsGenerator::areas()`](#method.areas), such as for a pass which connects the rooms.
+///
+/// Will create a map 40 tiles wide by 30 tiles high, filled with wall, and carve out up to 8 rooms in it.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let rooms_generator = OverlapRoomsGenerator::new(
+///     8,
+///     SizeRange::new(Size::new(3, 3), Size::new(8, 6)),
+///     50,
+/// );
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(40, 30), TileType::Wall))
+///     .build();
+/// rooms_generator.dun_gen_map(map_id);
+///
+/// let areas = rooms_generator.areas(map_id);
+/// assert!(!areas.is_empty() && areas.len() <= 8);
+///
+/// // No two rooms overlap, or even touch.
+/// for (index, area) in areas.iter().enumerate() {
+///     for other in &areas[index + 1..] {
+///         let right = area.position().x() + area.size().width() as i32;
+///         let bottom = area.position().y() + area.size().height() as i32;
+///         let other_right = other.position().x() + other.size().width() as i32;
+///         let other_bottom = other.position().y() + other.size().height() as i32;
+///         let apart = right < other.position().x()
+///             || other_right < area.position().x()
+///             || bottom < other.position().y()
+///             || other_bottom < area.position().y();
+///         assert!(apart);
+///     }
+/// }
+///
+/// // The floor is made up of exactly the rooms.
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// let mut room_tile_count = 0;
+/// for area in &areas {
+///     for y in 0..area.size().height() as i32 {
+///         for x in 0..area.size().width() as i32 {
+///             let position = *area.position() + Position::new(x, y);
+///             assert!(map.tile_type_at_local(position) == Some(TileType::Floor));
+///             room_tile_count += 1;
+///         }
+///     }
+/// }
+/// assert!(map.count_tiles(TileType::Floor) == room_tile_count);
+/// ```
+pub struct OverlapRoomsGenerator<TProvidesCount>
+where
+    TProvidesCount: ProvidesCount + Sized,
+{
+    provides_count: TProvidesCount,
+    size_range: SizeRange,
+    max_tries: usize,
+    areas: RwLock<HashMap<MapId, Vec<Area>>>,
+}
+
+impl<TProvidesCount> OverlapRoomsGenerator<TProvidesCount>
+where
+    TProvidesCount: ProvidesCount + Sized,
+{
+    /// Creates a new generator for scattering non-overlapping rooms, trying each room at up to `max_tries` positions.
+    pub fn new(provides_count: TProvidesCount, size_range: SizeRange, max_tries: usize) -> Self {
+        Self {
+            provides_count,
+            size_range,
+            max_tries,
+            areas: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the areas of the rooms placed on the map with the given `MapId`, in the order they were placed. Returns an empty `Vec` if no rooms were placed on the map.
+    pub fn areas(&self, map_id: MapId) -> Vec<Area> {
+        self.areas
+            .read()
+            .unwrap()
+            .get(&map_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn overlaps_with_buffer(area: &Area, other: &Area) -> bool {
+        let (left, top) = (area.position().x(), area.position().y());
+        let (right, bottom) = (
+            left + area.size().width() as i32,
+            top + area.size().height() as i32,
+        );
+        let (other_left, other_top) = (other.position().x(), other.position().y());
+        let (other_right, other_bottom) = (
+            other_left + other.size().width() as i32,
+            other_top + other.size().height() as i32,
+        );
+
+        // The right and bottom are exclusive; the 1-tile buffer makes touching rooms overlap.
+        left <= other_right && other_left <= right && top <= other_bottom && other_top <= bottom
+    }
+}
+
+impl<TProvidesCount> DoesDunGen for OverlapRoomsGenerator<TProvidesCount>
+where
+    TProvidesCount: ProvidesCount + Sized,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let width = map.size().width() as i32;
+        let height = map.size().height() as i32;
+        let mut rng = dun_gen_rng();
+        let mut areas = Vec::<Area>::new();
+        let count = self.provides_count.provide_count();
+        for _ in 0..count {
+            let size = self.size_range.provide_size();
+            let (room_width, room_height) = (size.width() as i32, size.height() as i32);
+            if room_width == 0 || room_height == 0 || room_width > width || room_height > height {
+                continue;
+            }
+
+            for _ in 0..self.max_tries {
+                let position = Position::new(
+                    rng.gen_range(0, width - room_width + 1),
+                    rng.gen_range(0, height - room_height + 1),
+                );
+                let area = Area::new(position, size);
+                if areas
+                    .iter()
+                    .any(|other| Self::overlaps_with_buffer(&area, other))
+                {
+                    continue;
+                }
+
+                for y in 0..room_height {
+                    for x in 0..room_width {
+                        map.tile_type_at_local_set(position + Position::new(x, y), TileType::Floor);
+                    }
+                }
+                areas.push(area);
+                break;
+            }
+        }
+
+        self.areas.write().unwrap().insert(map_id, areas);
+    }
+}
@@ -0,0 +1,81 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for placing a regular grid of pillars inside a room.
+///
+/// The `PillarsGenerator` places single [`TileType`](enum.TileType.html)::Wall tiles on a grid with the given [`Size`](geometry/struct.Size.html) of spacing between them, starting at the given margin from the edges of the map, and ending no closer than the margin to the far edges. Pillars are only placed on `TileType::Floor` tiles, and never on the outer ring of the map, so walls and `TileType::Portal`s are left as-is.
+///
+/// Will create a walled room with a `Size` of 13 tiles wide by 13 tiles high, and place pillars every 4 tiles, 2 tiles in from the edges.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(13, 13)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(PillarsGenerator::new(Size::new(4, 4), 2))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// // The walls of the room, and a 3 by 3 grid of pillars.
+/// assert!(map.count_tiles(TileType::Wall) == (13 * 4 - 4) + (3 * 3));
+/// for y in 1..12 {
+///     for x in 1..12 {
+///         let position = Position::new(x, y);
+///         let is_pillar = [2, 6, 10].contains(&x) && [2, 6, 10].contains(&y);
+///         assert!(is_pillar == (map.tile_type_at_local(position) == Some(TileType::Wall)));
+///     }
+/// }
+/// ```
+pub struct PillarsGenerator {
+    spacing: Size,
+    margin: u32,
+}
+
+impl PillarsGenerator {
+    /// Creates a new generator for placing pillars with the given spacing, and margin from the edges of the map.
+    pub fn new(spacing: Size, margin: u32) -> Self {
+        Self { spacing, margin }
+    }
+}
+
+impl DoesDunGen for PillarsGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let width = map.size().width();
+        let height = map.size().height();
+        // Never place pillars on the outer ring of the map.
+        let first = self.margin.max(1);
+        let spacing_x = self.spacing.width().max(1) as usize;
+        let spacing_y = self.spacing.height().max(1) as usize;
+        if width < first * 2 + 1 || height < first * 2 + 1 {
+            return;
+        }
+
+        for y in (self.margin..=(height - 1 - first)).step_by(spacing_y) {
+            for x in (self.margin..=(width - 1 - first)).step_by(spacing_x) {
+                let position = Position::new(x as i32, y as i32);
+                if x >= first
+                    && y >= first
+                    && map.tile_type_at_local(position) == Some(TileType::Floor)
+                {
+                    map.tile_type_at_local_set(position, TileType::Wall);
+                }
+            }
+        }
+    }
+}
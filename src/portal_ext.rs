@@ -0,0 +1,69 @@
+// External includes.
+use lazy_static::lazy_static;
+
+// Standard includes.
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// The labels of a map's portals, keyed by each portal's local `Position` and target `MapId`.
+pub(crate) type PortalLabels = HashMap<(Position, MapId), String>;
+
+lazy_static! {
+    /// Portal labels, keyed by the `MapId` of the map the portals are on.
+    pub(crate) static ref PORTAL_LABELS: RwLock<HashMap<MapId, PortalLabels>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Moves the label of the portal on the given map with the given local position and target, if it has one, to the given new local position and target.
+pub(crate) fn move_portal_label(
+    map_id: MapId,
+    (local_position, target): (Position, MapId),
+    (new_local_position, new_target): (Position, MapId),
+) {
+    let mut portal_labels = PORTAL_LABELS.write().unwrap();
+    if let Some(labels) = portal_labels.get_mut(&map_id) {
+        if let Some(label) = labels.remove(&(local_position, target)) {
+            labels.insert((new_local_position, new_target), label);
+        }
+    }
+}
+
+/// Helper methods for a [`Portal`](struct.Portal.html); implemented for `Portal`.
+///
+/// A portal can be given a label, such as "locked", "secret", or "boss entrance", with [`MapExt::add_portal_labeled()`](trait.MapExt.html#method.add_portal_labeled) or [`MapExt::set_portal_label()`](trait.MapExt.html#method.set_portal_label), to mark it during generation, and filter on it later; for example, in the filter of a [`MergePortalMapsAsSubMapsGenerator`](struct.MergePortalMapsAsSubMapsGenerator.html). Labels are stored by the `MapId` of the map the portal is on, and the portal's local [`Position`](geometry/struct.Position.html) and target; the generators in this crate which move or copy portals, such as [`TrimToContentGenerator`](struct.TrimToContentGenerator.html), [`ScaleRoomGenerator`](struct.ScaleRoomGenerator.html), and [`MergeMapsGenerator`](struct.MergeMapsGenerator.html), and [`clone_map()`](fn.clone_map.html), move or copy their labels with them. Changing a portal's position or target directly leaves its label behind.
+pub trait PortalExt {
+    /// Returns the label of the portal, if it has one, where the portal is on the map with the given `MapId`.
+    fn label(&self, map_id: MapId) -> Option<String>;
+
+    /// Points the portal, which is on the map with the given `MapId`, at a different map, keeping its position, facing, and label.
+    fn set_target(&mut self, map_id: MapId, target: MapId);
+}
+
+impl PortalExt for Portal {
+    fn label(&self, map_id: MapId) -> Option<String> {
+        PORTAL_LABELS
+            .read()
+            .unwrap()
+            .get(&map_id)
+            .and_then(|labels| labels.get(&(*self.local_position(), self.target())))
+            .cloned()
+    }
+
+    fn set_target(&mut self, map_id: MapId, target: MapId) {
+        move_portal_label(
+            map_id,
+            (*self.local_position(), self.target()),
+            (*self.local_position(), target),
+        );
+        *self = Portal::new(
+            *self.local_position(),
+            *self.portal_to_map_facing(),
+            *self.portal_to_map_position(),
+            target,
+        );
+    }
+}
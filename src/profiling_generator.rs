@@ -0,0 +1,81 @@
+// External includes.
+
+// Standard includes.
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// Internal includes.
+use super::*;
+
+/// Used to time a dungeon generator, for finding the slow steps of a long generation pipeline.
+///
+/// The `ProfilingGenerator` runs the inner generator, and adds its name and the time it took to run to the given sink, each time it is run.
+///
+/// The following code will time a generator which sleeps for a few milliseconds.
+///```
+/// # use dungen_minion::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::time::Duration;
+///
+/// struct SleepGenerator {}
+///
+/// impl DoesDunGen for SleepGenerator {
+///     fn dun_gen_map(&self, _map_id: MapId) {
+///         std::thread::sleep(Duration::from_millis(5));
+///     }
+/// }
+///
+/// let timings = Arc::new(Mutex::new(Vec::new()));
+/// DunGen::new(SparseMap::new())
+///     .gen_with(ProfilingGenerator::new("sleep", timings.clone(), SleepGenerator {}));
+///
+/// let timings = timings.lock().unwrap();
+/// assert!(timings.len() == 1);
+/// assert!(timings[0].0 == "sleep");
+/// assert!(timings[0].1 >= Duration::from_millis(5));
+///```
+pub struct ProfilingGenerator<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    name: String,
+    sink: Arc<Mutex<Vec<(String, Duration)>>>,
+    dun_gen: TDunGen,
+}
+
+impl<TDunGen> ProfilingGenerator<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    /// Creates a dungeon generator that times the inner generator under the given name.
+    pub fn new(name: &str, sink: Arc<Mutex<Vec<(String, Duration)>>>, dun_gen: TDunGen) -> Self {
+        Self {
+            name: name.to_string(),
+            sink,
+            dun_gen,
+        }
+    }
+
+    fn record(&self, elapsed: Duration) {
+        if let Ok(mut sink) = self.sink.lock() {
+            sink.push((self.name.clone(), elapsed));
+        }
+    }
+}
+
+impl<TDunGen> DoesDunGen for ProfilingGenerator<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let start = Instant::now();
+        self.dun_gen.dun_gen(target);
+        self.record(start.elapsed());
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let start = Instant::now();
+        self.dun_gen.dun_gen_map(map_id);
+        self.record(start.elapsed());
+    }
+}
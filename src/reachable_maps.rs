@@ -0,0 +1,51 @@
+// External includes.
+
+// Standard includes.
+use std::collections::{HashSet, VecDeque};
+
+// Internal includes.
+use super::*;
+
+/// Returns the `MapId`s of every [`Map`](trait.Map.html) reachable from the given map through [`Portal`](struct.Portal.html)s, including the given map.
+///
+/// The maps are returned in breadth-first order, starting with the given map; each map is only returned once, even if the portals form cycles.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 8)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(3, Box::new(|| SparseMap::new())))
+///     .gen_with(TraversePortalsGenerator::new(SequentialGenerator::new(&[
+///         &EmptyRoomGenerator::new(Size::new(8, 6)),
+///         &WalledRoomGenerator::new(Size::zero()),
+///     ])))
+///     .gen_with(TraverseThisAndPortalsGenerator::new(ReciprocatePortalsGenerator::new()))
+///     .build();
+///
+/// let reachable = reachable_maps(map_id);
+/// assert!(reachable.len() == 4);
+/// assert!(reachable[0] == map_id);
+/// ```
+pub fn reachable_maps(map_id: MapId) -> Vec<MapId> {
+    let maps = &MAPS.read();
+    let mut visited = HashSet::new();
+    let mut output = Vec::new();
+    let mut on_maps = VecDeque::new();
+    visited.insert(map_id);
+    on_maps.push_back(map_id);
+
+    while let Some(map_id) = on_maps.pop_front() {
+        output.push(map_id);
+        let map = &maps[map_id].read();
+        for portal in map.portals() {
+            let target_map_id = portal.target();
+            if visited.insert(target_map_id) {
+                on_maps.push_back(target_map_id);
+            }
+        }
+    }
+
+    output
+}
@@ -1,10 +1,11 @@
 // External includes.
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 // Standard includes.
 
 // Internal includes.
 use super::*;
+use crate::dun_gen_rng::dun_gen_rng;
 use crate::geometry::*;
 
 /// A generator for iterating through the [`Portal`](struct.Portal.html)s on a [`Map`](trait.Map.html), and creating one or more linking `Portal`s on the target map, if they do not exist.
@@ -72,6 +73,41 @@ use crate::geometry::*;
 ///     assert!(portal_count >= 2 && portal_count <= 5);
 /// })
 /// ```
+///
+/// Target maps smaller than 3 tiles wide or high have no room for a portal on their edge, and are skipped; the remaining target maps are still given their matching portals.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 8)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(3, Box::new(|| SparseMap::new())))
+///     .build();
+///
+/// let targets = MAPS.read()[map_id]
+///     .read()
+///     .portals()
+///     .into_iter()
+///     .map(|portal| portal.target())
+///     .collect::<Vec<_>>();
+/// // The first target map is too small to hold a portal.
+/// EmptyRoomGenerator::new(Size::new(2, 2)).dun_gen_map(targets[0]);
+/// for target in &targets[1..] {
+///     EmptyRoomGenerator::new(Size::new(8, 6)).dun_gen_map(*target);
+///     WalledRoomGenerator::new(Size::zero()).dun_gen_map(*target);
+/// }
+///
+/// ReciprocatePortalsGenerator::new().dun_gen_map(map_id);
+///
+/// let maps = MAPS.read();
+/// assert!(maps[targets[0]].read().portal_count() == 0);
+/// for target in &targets[1..] {
+///     let target_map = maps[*target].read();
+///     assert!(target_map.portal_count() == 1);
+///     assert!(target_map.get_portal_at(0).unwrap().target() == map_id);
+/// }
+/// ```
 pub struct ReciprocatePortalsGenerator {}
 
 impl ReciprocatePortalsGenerator {
@@ -103,7 +139,7 @@ impl DoesDunGen for ReciprocatePortalsGenerator {
             let target_map_mut = &mut maps[target_map_id].write();
             let target_map_size = *target_map_mut.size();
             if target_map_size.width() < 3 || target_map_size.height() < 3 {
-                return;
+                continue;
             }
 
             let mut found_match = false;
@@ -114,7 +150,7 @@ impl DoesDunGen for ReciprocatePortalsGenerator {
             }
 
             if !found_match {
-                let mut rng = thread_rng();
+                let mut rng = dun_gen_rng();
                 let portal_facing = *portal_mut.portal_to_map_facing();
                 let (portal_x, portal_y) = match portal_facing {
                     CardinalDirection::North => {
@@ -0,0 +1,115 @@
+// External includes.
+
+// Standard includes.
+use std::collections::{HashSet, VecDeque};
+
+// Internal includes.
+use super::*;
+
+/// Used to execute a dungeon generator on a map, and on every map reachable from it through portals.
+///
+/// The `RecursiveTraverseGenerator` runs the inner generator on the map, and then follows its portals, breadth-first, running the inner generator on each map it reaches. Each map is only visited once per run, so cycles of portals, such as those created by [`ReciprocatePortalsGenerator`](struct.ReciprocatePortalsGenerator.html), are safe. Portals added by the inner generator are followed as well.
+///
+/// Unlike [`TraverseThisAndPortalsGenerator`](struct.TraverseThisAndPortalsGenerator.html), which only reaches the maps one portal away, every reachable map is visited. The visited maps are forgotten at the end of each run, so the same generator can be run again on the same maps.
+///
+/// The following code will run a generator on each of two rooms joined by reciprocal portals, once each.
+///```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// use std::cell::Cell;
+///
+/// struct CountingGenerator<'a> {
+///     count: &'a Cell<usize>,
+/// }
+///
+/// impl<'a> DoesDunGen for CountingGenerator<'a> {
+///     fn dun_gen_map(&self, _map_id: MapId) {
+///         self.count.set(self.count.get() + 1);
+///     }
+/// }
+///
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(1, Box::new(|| SparseMap::new())))
+///     .gen_with(TraversePortalsGenerator::new(SequentialGenerator::new(&[
+///         &EmptyRoomGenerator::new(Size::new(4, 4)),
+///         &WalledRoomGenerator::new(Size::zero()),
+///     ])))
+///     .gen_with(TraverseThisAndPortalsGenerator::new(ReciprocatePortalsGenerator::new()))
+///     .build();
+/// assert!(reachable_maps(map_id).len() == 2);
+///
+/// let count = Cell::new(0);
+/// let traverse = RecursiveTraverseGenerator::new(CountingGenerator { count: &count });
+/// traverse.dun_gen_map(map_id);
+/// assert!(count.get() == 2);
+///
+/// // The same generator visits every map again on the next run.
+/// traverse.dun_gen_map(map_id);
+/// assert!(count.get() == 4);
+///```
+pub struct RecursiveTraverseGenerator<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    dun_gen: TDunGen,
+}
+
+impl<TDunGen> RecursiveTraverseGenerator<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    /// Creates a dungeon generator that traverses the current map, and every map reachable from it.
+    pub fn new(dun_gen: TDunGen) -> Self {
+        Self { dun_gen }
+    }
+
+    fn traverse_portals(
+        &self,
+        map_id: MapId,
+        visited_maps: &mut HashSet<MapId>,
+        on_maps: &mut VecDeque<MapId>,
+    ) {
+        let maps = &MAPS.read();
+        let map = &maps[map_id].read();
+        for portal in map.portals() {
+            let target_map_id = portal.target();
+            if visited_maps.insert(target_map_id) {
+                on_maps.push_back(target_map_id);
+            }
+        }
+    }
+}
+
+impl<TDunGen> DoesDunGen for RecursiveTraverseGenerator<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        let mut visited_maps = HashSet::new();
+        let mut on_maps = VecDeque::new();
+        visited_maps.insert(map_id);
+
+        self.dun_gen.dun_gen(target);
+        self.traverse_portals(map_id, &mut visited_maps, &mut on_maps);
+        while let Some(map_id) = on_maps.pop_front() {
+            self.dun_gen.dun_gen_map(map_id);
+            self.traverse_portals(map_id, &mut visited_maps, &mut on_maps);
+        }
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let mut visited_maps = HashSet::new();
+        let mut on_maps = VecDeque::new();
+        visited_maps.insert(map_id);
+        on_maps.push_back(map_id);
+
+        while let Some(map_id) = on_maps.pop_front() {
+            self.dun_gen.dun_gen_map(map_id);
+            self.traverse_portals(map_id, &mut visited_maps, &mut on_maps);
+        }
+    }
+}
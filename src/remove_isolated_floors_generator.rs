@@ -0,0 +1,123 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+
+/// A generator for walling off small, disconnected areas of floor.
+///
+/// The `RemoveIsolatedFloorsGenerator` finds each group of connected [`TileType`](enum.TileType.html)::Floor tiles on the map; by default, tiles are only connected orthogonally, but diagonal tiles can also be connected with [`RemoveIsolatedFloorsGenerator::with_connectivity()`](#method.with_connectivity). By default, every group except the largest is converted to `TileType::Wall`. If a minimum size is provided with [`RemoveIsolatedFloorsGenerator::with_min_size()`](#method.with_min_size), only the groups with fewer tiles than the minimum size are converted, instead.
+///
+/// Will create a cave with a 2-tile pocket of floor next to it, and wall off the pocket.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(12, 8), TileType::Wall))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(1, 1), Size::new(6, 6)),
+///         TileType::Floor))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(9, 2), Size::new(1, 2)),
+///         TileType::Floor))
+///     .gen_with(RemoveIsolatedFloorsGenerator::new())
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(map.count_tiles(TileType::Floor) == 36);
+/// assert!(map.tile_type_at_local(Position::new(9, 2)) == Some(TileType::Wall));
+/// assert!(map.tile_type_at_local(Position::new(9, 3)) == Some(TileType::Wall));
+/// for y in 1..7 {
+///     for x in 1..7 {
+///         assert!(map.tile_type_at_local(Position::new(x, y)) == Some(TileType::Floor));
+///     }
+/// }
+/// ```
+///
+/// A pocket which touches the cave at a corner is kept, when diagonal tiles are connected.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(12, 8), TileType::Wall))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(1, 1), Size::new(6, 6)),
+///         TileType::Floor))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(7, 7), Size::new(1, 1)),
+///         TileType::Floor))
+///     .gen_with(RemoveIsolatedFloorsGenerator::new().with_connectivity(Connectivity::Eight))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(map.count_tiles(TileType::Floor) == 37);
+/// assert!(map.tile_type_at_local(Position::new(7, 7)) == Some(TileType::Floor));
+/// ```
+pub struct RemoveIsolatedFloorsGenerator {
+    min_size: Option<usize>,
+    connectivity: Connectivity,
+}
+
+impl RemoveIsolatedFloorsGenerator {
+    /// Creates a new generator for walling off all but the largest group of floor tiles.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            min_size: None,
+            connectivity: Connectivity::Four,
+        }
+    }
+
+    /// Sets the minimum number of tiles a group of floor tiles must have to be kept, instead of keeping only the largest group.
+    pub fn with_min_size(mut self, min_size: usize) -> Self {
+        self.min_size = Some(min_size);
+        self
+    }
+
+    /// Sets which neighbouring floor tiles count as connected. By default, only orthogonal neighbours, `Connectivity::Four`, are connected.
+    pub fn with_connectivity(mut self, connectivity: Connectivity) -> Self {
+        self.connectivity = connectivity;
+        self
+    }
+}
+
+impl DoesDunGen for RemoveIsolatedFloorsGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let components = map.floor_connected_components(self.connectivity);
+
+        let largest_index = components
+            .iter()
+            .enumerate()
+            .max_by_key(|(index, component)| (component.len(), std::cmp::Reverse(*index)))
+            .map(|(index, _)| index);
+
+        for (index, component) in components.iter().enumerate() {
+            let remove = match self.min_size {
+                Some(min_size) => component.len() < min_size,
+                None => Some(index) != largest_index,
+            };
+            if !remove {
+                continue;
+            }
+
+            for position in component {
+                map.tile_type_at_local_set(*position, TileType::Wall);
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for reconciling the [`Portal`](struct.Portal.html)s on a [`Map`](trait.Map.html) with its [`TileType`](enum.TileType.html)::Portal tiles.
+///
+/// Every `Portal` will have a `TileType::Portal` stamped at its local [`Position`](geometry/struct.Position.html), and every `TileType::Portal` with no matching `Portal` will be replaced. By default, stray `TileType::Portal` tiles are replaced with `TileType::Wall`.
+///
+/// Will create a walled map with a `Size` of 8 tiles wide by 6 tiles high, with a single portal, and then break the map by removing the portal's tile and adding a stray `TileType::Portal`; the `RepairPortalsGenerator` will then fix both problems.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(1, Box::new(|| SparseMap::new())))
+///     .build();
+///
+/// let portal_position;
+/// let stray_position;
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     portal_position = *map.get_portal_at(0).unwrap().local_position();
+///     // Find a wall that does not have a portal on it.
+///     stray_position = if portal_position == Position::new(1, 0) {
+///         Position::new(2, 0)
+///     } else {
+///         Position::new(1, 0)
+///     };
+///     map.tile_type_at_local_set(portal_position, TileType::Wall);
+///     map.tile_type_at_local_set(stray_position, TileType::Portal);
+/// }
+///
+/// DunGen::new(map_id).gen_with(RepairPortalsGenerator::new());
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(map.portal_count() == 1);
+/// assert!(map.tile_type_at_local(portal_position) == Some(TileType::Portal));
+/// assert!(map.tile_type_at_local(stray_position) == Some(TileType::Wall));
+/// assert!(map.count_tiles(TileType::Portal) == 1);
+/// ```
+pub struct RepairPortalsGenerator {
+    replace_stray_with: TileType,
+}
+
+impl RepairPortalsGenerator {
+    /// Creates a new generator for repairing portals on a map. Stray `TileType::Portal` tiles will be replaced with `TileType::Wall`.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            replace_stray_with: TileType::Wall,
+        }
+    }
+
+    /// Creates a new generator for repairing portals on a map, replacing stray `TileType::Portal` tiles with the specified `TileType`.
+    pub fn with_replacement(replace_stray_with: TileType) -> Self {
+        Self { replace_stray_with }
+    }
+}
+
+impl DoesDunGen for RepairPortalsGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let portal_positions = map
+            .portals()
+            .into_iter()
+            .map(|portal| *portal.local_position())
+            .collect::<Vec<_>>();
+
+        for y in 0..map.size().height() {
+            for x in 0..map.size().width() {
+                let position = Position::new(x as i32, y as i32);
+                if map.tile_type_at_local(position) == Some(TileType::Portal)
+                    && !portal_positions.contains(&position)
+                {
+                    map.tile_type_at_local_set(position, self.replace_stray_with);
+                }
+            }
+        }
+
+        for position in portal_positions {
+            map.tile_type_at_local_set(position, TileType::Portal);
+        }
+    }
+}
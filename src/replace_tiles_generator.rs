@@ -0,0 +1,103 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::fractional_area::provide_placed_shape_within;
+use crate::geometry::*;
+
+/// A generator for replacing every tile of one [`TileType`](enum.TileType.html) with another.
+///
+/// By default, the `ReplaceTilesGenerator` replaces tiles across the whole of the map's [`Size`](geometry/struct.Size.html). It can be restricted to a region of the map with [`ReplaceTilesGenerator::within()`](#method.within); only tiles which intersect the region's shape are replaced.
+///
+/// Will create a walled map 8 tiles wide by 6 tiles high, and replace the walls along its left half with floor.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(
+///         ReplaceTilesGenerator::new(TileType::Wall, TileType::Floor)
+///             .within(Area::new(Position::zero(), Size::new(4, 6))))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// for y in 0..6 {
+///     for x in 0..8 {
+///         let tile_type = map.tile_type_at_local(Position::new(x, y));
+///         if x == 7 || ((y == 0 || y == 5) && x >= 4) {
+///             assert!(tile_type == Some(TileType::Wall));
+///         } else {
+///             assert!(tile_type == Some(TileType::Floor));
+///         }
+///     }
+/// }
+/// ```
+pub struct ReplaceTilesGenerator {
+    from: TileType,
+    to: TileType,
+    region: Option<Box<dyn ProvidesPlacedShape>>,
+}
+
+impl ReplaceTilesGenerator {
+    /// Creates a new generator for replacing every tile of the `from` `TileType` with the `to` `TileType`.
+    pub fn new(from: TileType, to: TileType) -> Self {
+        Self {
+            from,
+            to,
+            region: None,
+        }
+    }
+
+    /// Restricts the replacement to the tiles which intersect the given shape.
+    pub fn within<TProvidesPlacedShape>(
+        mut self,
+        provides_placed_shape: TProvidesPlacedShape,
+    ) -> Self
+    where
+        TProvidesPlacedShape: ProvidesPlacedShape + 'static,
+    {
+        self.region = Some(Box::new(provides_placed_shape));
+        self
+    }
+}
+
+impl DoesDunGen for ReplaceTilesGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let possible_area = Area::from(*map.size());
+        let region = self
+            .region
+            .as_ref()
+            .map(|region| provide_placed_shape_within(&**region, possible_area));
+
+        for y in 0..map.size().height() as i32 {
+            for x in 0..map.size().width() as i32 {
+                let position = Position::new(x, y);
+                if map.tile_type_at_local(position) != Some(self.from) {
+                    continue;
+                }
+
+                if let Some(region) = &region {
+                    if !region.intersects_position(position) {
+                        continue;
+                    }
+                }
+
+                map.tile_type_at_local_set(position, self.to);
+            }
+        }
+    }
+}
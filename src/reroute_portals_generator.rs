@@ -0,0 +1,103 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+
+/// A generator for pointing the portals of a map at different maps.
+///
+/// The `ReroutePortalsGenerator` calls the given function with each [`Portal`](struct.Portal.html) of the map. If the function returns a `MapId`, the portal is pointed at that map, keeping its position, facing, and label; if it returns `None`, the portal is left as-is. Useful for splicing rooms which have already been generated into a dungeon.
+///
+/// Will create a map with 4 portals, and point all of them at the same room.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let shared_map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(4, 4)))
+///     .build();
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(4, Box::new(|| SparseMap::new())))
+///     .gen_with(ReroutePortalsGenerator::new(|_portal: &Portal| Some(shared_map_id)))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// assert!(map.portal_count() == 4);
+/// for portal in map.portals() {
+///     assert!(portal.target() == shared_map_id);
+/// }
+/// ```
+///
+/// Will reroute two labelled portals to the same room; each keeps its own label.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let shared_map_id = SparseMap::new();
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .build();
+/// let (locked_map_id, secret_map_id) = (SparseMap::new(), SparseMap::new());
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     map.add_portal_labeled(
+///         Position::new(0, 2), CardinalDirection::East, Position::zero(), locked_map_id, "locked");
+///     map.add_portal_labeled(
+///         Position::new(7, 2), CardinalDirection::West, Position::zero(), secret_map_id, "secret");
+/// }
+///
+/// DunGen::new(map_id)
+///     .gen_with(ReroutePortalsGenerator::new(|_portal: &Portal| Some(shared_map_id)));
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+/// let west_portal = map.portal_at(Position::new(0, 2)).unwrap();
+/// let east_portal = map.portal_at(Position::new(7, 2)).unwrap();
+/// assert!(west_portal.target() == shared_map_id);
+/// assert!(east_portal.target() == shared_map_id);
+/// assert!(map.portal_label(west_portal) == Some("locked".to_string()));
+/// assert!(map.portal_label(east_portal) == Some("secret".to_string()));
+/// ```
+pub struct ReroutePortalsGenerator<TFunc>
+where
+    TFunc: Fn(&Portal) -> Option<MapId>,
+{
+    reroute: TFunc,
+}
+
+impl<TFunc> ReroutePortalsGenerator<TFunc>
+where
+    TFunc: Fn(&Portal) -> Option<MapId>,
+{
+    /// Creates a new generator for rerouting portals with the given function.
+    pub fn new(reroute: TFunc) -> Self {
+        Self { reroute }
+    }
+}
+
+impl<TFunc> DoesDunGen for ReroutePortalsGenerator<TFunc>
+where
+    TFunc: Fn(&Portal) -> Option<MapId>,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+        for portal in map.portals_mut() {
+            if let Some(target) = (self.reroute)(portal) {
+                portal.set_target(map_id, target);
+            }
+        }
+    }
+}
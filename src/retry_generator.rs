@@ -0,0 +1,146 @@
+// External includes.
+
+// Standard includes.
+use std::collections::HashSet;
+
+// Internal includes.
+use super::*;
+
+/// Used to re-run a dungeon generator until its output passes a check.
+///
+/// The `RetryGenerator` takes a copy of the map, runs the inner generator, and then calls the check with the map's `MapId`. If the check fails, the map is restored from the copy, any maps which the failed attempt added through [`Portal`](struct.Portal.html)s or [`SubMap`](struct.SubMap.html)s are freed with [`free_map()`](fn.free_map.html), and the inner generator is run again. If every one of the attempts fails, the output of the last attempt is kept.
+///
+/// Will create a cave of noise, regenerating it until all of its floor tiles are connected.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let is_connected = |map_id: MapId| {
+///     let maps = MAPS.read();
+///     let map = maps[map_id].read();
+///     let mut floors = Vec::new();
+///     map.visit_tiles(|position, tile_type| {
+///         if tile_type == TileType::Floor {
+///             floors.push(position);
+///         }
+///     });
+///     if floors.is_empty() {
+///         return false;
+///     }
+///
+///     let mut reached = std::collections::HashSet::new();
+///     let mut open = vec![floors[0]];
+///     reached.insert(floors[0]);
+///     while let Some(position) = open.pop() {
+///         for direction in &[Position::NORTH, Position::EAST, Position::SOUTH, Position::WEST] {
+///             let neighbour = position + *direction;
+///             if map.is_local_position_valid(neighbour)
+///                 && map.tile_type_at_local(neighbour) == Some(TileType::Floor)
+///                 && reached.insert(neighbour)
+///             {
+///                 open.push(neighbour);
+///             }
+///         }
+///     }
+///     reached.len() == floors.len()
+/// };
+///
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(16, 12)))
+///     .gen_with(RetryGenerator::new(
+///         1000,
+///         NoiseFloorGenerator::new(0.2, 0.4),
+///         is_connected,
+///     ))
+///     .build();
+///
+/// assert!(*MAPS.read()[map_id].read().size() == Size::new(16, 12));
+/// assert!(is_connected(map_id));
+/// ```
+pub struct RetryGenerator<TDunGen, TCheck>
+where
+    TDunGen: DoesDunGen,
+    TCheck: Fn(MapId) -> bool,
+{
+    max_attempts: usize,
+    dun_gen: TDunGen,
+    check: TCheck,
+}
+
+impl<TDunGen, TCheck> RetryGenerator<TDunGen, TCheck>
+where
+    TDunGen: DoesDunGen,
+    TCheck: Fn(MapId) -> bool,
+{
+    /// Creates a new generator which runs the inner generator up to `max_attempts` times, until its output passes the check.
+    pub fn new(max_attempts: usize, dun_gen: TDunGen, check: TCheck) -> Self {
+        Self {
+            max_attempts,
+            dun_gen,
+            check,
+        }
+    }
+
+    fn referenced_maps(map: &dyn Map) -> HashSet<MapId> {
+        map.portals()
+            .into_iter()
+            .map(|portal| portal.target())
+            .chain(map.sub_maps().into_iter().map(|sub_map| sub_map.value()))
+            .collect()
+    }
+}
+
+impl<TDunGen, TCheck> DoesDunGen for RetryGenerator<TDunGen, TCheck>
+where
+    TDunGen: DoesDunGen,
+    TCheck: Fn(MapId) -> bool,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let (original, original_references) = {
+            let maps = &MAPS.read();
+            let map = &maps[map_id].read();
+            (map.box_clone(), Self::referenced_maps(map.as_ref()))
+        };
+
+        for attempt in 0..self.max_attempts {
+            self.dun_gen.dun_gen_map(map_id);
+            if (self.check)(map_id) || attempt + 1 == self.max_attempts {
+                break;
+            }
+
+            let mut added_maps = {
+                let maps = &MAPS.read();
+                let map = &mut maps[map_id].write();
+                let added_maps = Self::referenced_maps(map.as_ref())
+                    .difference(&original_references)
+                    .copied()
+                    .collect::<Vec<_>>();
+                **map = original.box_clone();
+                added_maps
+            };
+
+            // Maps added by the failed attempt may have had maps added to them, in turn.
+            let mut invalidated = HashSet::new();
+            while let Some(added_map_id) = added_maps.pop() {
+                if added_map_id == map_id
+                    || original_references.contains(&added_map_id)
+                    || !invalidated.insert(added_map_id)
+                {
+                    continue;
+                }
+
+                {
+                    let maps = &MAPS.read();
+                    let added_map = &maps[added_map_id].read();
+                    added_maps.extend(Self::referenced_maps(added_map.as_ref()));
+                }
+                free_map(added_map_id);
+            }
+        }
+    }
+}
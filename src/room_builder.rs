@@ -0,0 +1,86 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A builder for putting together a map by hand, without a chain of generators.
+///
+/// Each method of the `RoomBuilder` is applied to the map straight away. For anything more involved, use [`DunGen`](struct.DunGen.html), which the `RoomBuilder` can be swapped for at any point by passing the built `MapId` to `DunGen::new()`.
+///
+/// Will build a walled room 6 tiles wide by 5 tiles high, with a portal in its west wall.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let target_map_id = SparseMap::new();
+/// let map_id = RoomBuilder::new()
+///     .wall(Area::new(Position::zero(), Size::new(6, 5)))
+///     .floor(Area::new(Position::new(1, 1), Size::new(4, 3)))
+///     .portal(Position::new(0, 2), CardinalDirection::East, target_map_id)
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(*map.size() == Size::new(6, 5));
+/// assert!(map.count_tiles(TileType::Floor) == 4 * 3);
+/// assert!(map.count_tiles(TileType::Wall) == (6 * 5) - (4 * 3) - 1);
+/// assert!(map.tile_type_at_local(Position::new(0, 0)) == Some(TileType::Wall));
+/// assert!(map.tile_type_at_local(Position::new(1, 1)) == Some(TileType::Floor));
+/// assert!(map.tile_type_at_local(Position::new(0, 2)) == Some(TileType::Portal));
+///
+/// assert!(map.portal_count() == 1);
+/// let portal = map.portal_at(Position::new(0, 2)).unwrap();
+/// assert!(portal.target() == target_map_id);
+/// assert!(*portal.portal_to_map_facing() == CardinalDirection::East);
+/// ```
+pub struct RoomBuilder {
+    map_id: MapId,
+}
+
+impl RoomBuilder {
+    /// Creates a new builder for an empty [`SparseMap`](struct.SparseMap.html).
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            map_id: SparseMap::new(),
+        }
+    }
+
+    /// Fills the given area of the map with `TileType::Floor`.
+    pub fn floor(&mut self, area: Area) -> &mut Self {
+        FillTilesGenerator::new(area, TileType::Floor).dun_gen_map(self.map_id);
+        self
+    }
+
+    /// Fills the given area of the map with `TileType::Wall`.
+    pub fn wall(&mut self, area: Area) -> &mut Self {
+        FillTilesGenerator::new(area, TileType::Wall).dun_gen_map(self.map_id);
+        self
+    }
+
+    /// Adds a [`Portal`](struct.Portal.html) to the map, at the given local position, facing in the given direction.
+    pub fn portal(
+        &mut self,
+        local_position: Position,
+        portal_to_map_facing: CardinalDirection,
+        target: MapId,
+    ) -> &mut Self {
+        let maps = &MAPS.read();
+        let map = &mut maps[self.map_id].write();
+        map.add_portal(
+            local_position,
+            portal_to_map_facing,
+            Position::zero(),
+            target,
+        );
+        self
+    }
+
+    /// Returns the `MapId` of the built [`Map`](trait.Map.html).
+    pub fn build(&mut self) -> MapId {
+        self.map_id
+    }
+}
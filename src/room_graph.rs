@@ -0,0 +1,120 @@
+// External includes.
+
+// Standard includes.
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// Internal includes.
+use super::*;
+
+/// An undirected graph of the [`Map`](trait.Map.html)s in a dungeon, connected by their [`Portal`](struct.Portal.html)s.
+///
+/// Each map is a node, and each pair of maps connected by one or more portals, in either direction, is a single edge. Built with [`build_room_graph()`](fn.build_room_graph.html).
+pub struct RoomGraph {
+    nodes: Vec<MapId>,
+    edges: Vec<(MapId, MapId)>,
+    neighbors: HashMap<MapId, Vec<MapId>>,
+}
+
+impl RoomGraph {
+    /// The `MapId`s of every map in the graph, in breadth-first order from the root map.
+    pub fn nodes(&self) -> &[MapId] {
+        &self.nodes
+    }
+
+    /// The pairs of `MapId`s connected by portals; each pair is only listed once.
+    pub fn edges(&self) -> &[(MapId, MapId)] {
+        &self.edges
+    }
+
+    /// The `MapId`s of the maps connected to the given map; empty if the map is not in the graph.
+    pub fn neighbors(&self, map_id: MapId) -> &[MapId] {
+        self.neighbors
+            .get(&map_id)
+            .map(|neighbors| neighbors.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// The number of maps in the graph.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The number of connected pairs of maps in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+/// Builds a [`RoomGraph`](struct.RoomGraph.html) of every map reachable from the given map through [`Portal`](struct.Portal.html)s.
+///
+/// The portals are traversed breadth-first from the given map; each map is only visited once, even if the portals form cycles.
+///
+/// Will create a hub room with 5 portal rooms around it, with the portals reciprocated, and build its graph.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(16, 12)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(5, Box::new(|| SparseMap::new())))
+///     .gen_with(TraversePortalsGenerator::new(SequentialGenerator::new(&[
+///         &EmptyRoomGenerator::new(Size::new(8, 6)),
+///         &WalledRoomGenerator::new(Size::zero()),
+///     ])))
+///     .gen_with(TraverseThisAndPortalsGenerator::new(ReciprocatePortalsGenerator::new()))
+///     .build();
+///
+/// let room_graph = build_room_graph(map_id);
+/// assert!(room_graph.node_count() == 6);
+/// assert!(room_graph.edge_count() == 5);
+/// assert!(room_graph.nodes()[0] == map_id);
+/// assert!(room_graph.neighbors(map_id).len() == 5);
+/// for neighbor in room_graph.neighbors(map_id) {
+///     assert!(room_graph.neighbors(*neighbor) == &[map_id]);
+/// }
+/// ```
+pub fn build_room_graph(root: MapId) -> RoomGraph {
+    let maps = &MAPS.read();
+    let mut visited = HashSet::new();
+    let mut connected = HashSet::new();
+    let mut room_graph = RoomGraph {
+        nodes: Vec::new(),
+        edges: Vec::new(),
+        neighbors: HashMap::new(),
+    };
+    let mut on_maps = VecDeque::new();
+    visited.insert(root);
+    on_maps.push_back(root);
+
+    while let Some(map_id) = on_maps.pop_front() {
+        room_graph.nodes.push(map_id);
+        room_graph.neighbors.entry(map_id).or_default();
+        let map = &maps[map_id].read();
+        for portal in map.portals() {
+            let target_map_id = portal.target();
+            if visited.insert(target_map_id) {
+                on_maps.push_back(target_map_id);
+            }
+
+            let edge = (map_id.min(target_map_id), map_id.max(target_map_id));
+            if connected.insert(edge) {
+                room_graph.edges.push((map_id, target_map_id));
+                room_graph
+                    .neighbors
+                    .entry(map_id)
+                    .or_default()
+                    .push(target_map_id);
+                if target_map_id != map_id {
+                    room_graph
+                        .neighbors
+                        .entry(target_map_id)
+                        .or_default()
+                        .push(map_id);
+                }
+            }
+        }
+    }
+
+    room_graph
+}
@@ -0,0 +1,156 @@
+// External includes.
+
+// Standard includes.
+use std::collections::HashMap;
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+use crate::portal_ext::move_portal_label;
+
+/// A generator for scaling up a map by a whole-number factor.
+///
+/// The `ScaleRoomGenerator` replaces each tile of the map with a block of `factor` by `factor` tiles of the same [`TileType`](enum.TileType.html), and multiplies the map's [`Size`](geometry/struct.Size.html) by `factor`. This allows a map to be designed small, and then blown up to playable dimensions.
+///
+/// Each [`Portal`](struct.Portal.html) is moved to a single representative tile of its block; the middle of the block, or the side of it on the edge of the map, for a portal on the edge of the map. The other tiles of a portal's block are set to `TileType::Floor`, or to `TileType::Wall` if they are on the edge of the map, so that a door in a wall remains a door in a wall. The positions of [`SubMap`](struct.SubMap.html)s are scaled as well. Portals on other maps which lead to this map are not changed.
+///
+/// Will create a walled room 4 tiles wide by 3 tiles high, with a portal on its west wall, and scale it up to 8 tiles wide by 6 tiles high.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(4, 3)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .build();
+/// let target_map_id = SparseMap::new();
+///
+/// let original = {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     map.add_portal(Position::new(0, 1), CardinalDirection::East, Position::zero(), target_map_id);
+///     map.box_clone()
+/// };
+///
+/// DunGen::new(map_id)
+///     .gen_with(ScaleRoomGenerator::new(2));
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(*map.size() == Size::new(8, 6));
+/// for y in 0..6 {
+///     for x in 0..8 {
+///         let position = Position::new(x, y);
+///         let original_position = Position::new(x / 2, y / 2);
+///         if original_position == Position::new(0, 1) {
+///             continue;
+///         }
+///         assert!(map.tile_type_at_local(position) == original.tile_type_at_local(original_position));
+///     }
+/// }
+///
+/// for portal in map.portals() {
+///     assert!(*portal.local_position() == Position::new(0, 3));
+/// }
+/// assert!(map.tile_type_at_local(Position::new(0, 3)) == Some(TileType::Portal));
+/// assert!(map.tile_type_at_local(Position::new(0, 2)) == Some(TileType::Wall));
+/// assert!(map.tile_type_at_local(Position::new(1, 2)) == Some(TileType::Floor));
+/// assert!(map.tile_type_at_local(Position::new(1, 3)) == Some(TileType::Floor));
+/// ```
+pub struct ScaleRoomGenerator {
+    factor: u32,
+}
+
+impl ScaleRoomGenerator {
+    /// Creates a new generator for scaling up a map by the given factor.
+    pub fn new(factor: u32) -> Self {
+        Self { factor }
+    }
+
+    fn scale_axis(&self, value: i32, length: u32) -> i32 {
+        let factor = self.factor as i32;
+        let offset = if value == 0 {
+            0
+        } else if value == length as i32 - 1 {
+            factor - 1
+        } else {
+            factor / 2
+        };
+
+        value * factor + offset
+    }
+}
+
+impl DoesDunGen for ScaleRoomGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        if self.factor <= 1 {
+            return;
+        }
+
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let size = *map.size();
+        let mut old_tiles = HashMap::new();
+        map.visit_tiles(|position, tile_type| {
+            old_tiles.insert(position, tile_type);
+        });
+
+        let factor = self.factor as i32;
+        let new_size = Size::new(size.width() * self.factor, size.height() * self.factor);
+        let is_edge = |position: Position| {
+            position.x() == 0
+                || position.y() == 0
+                || position.x() == new_size.width() as i32 - 1
+                || position.y() == new_size.height() as i32 - 1
+        };
+
+        map.resize(new_size);
+        for (position, tile_type) in old_tiles {
+            for y in 0..factor {
+                for x in 0..factor {
+                    let new_position =
+                        Position::new(position.x() * factor + x, position.y() * factor + y);
+                    let new_tile_type = match tile_type {
+                        TileType::Portal if is_edge(new_position) => TileType::Wall,
+                        TileType::Portal => TileType::Floor,
+                        tile_type => tile_type,
+                    };
+                    map.tile_type_at_local_set(new_position, new_tile_type);
+                }
+            }
+        }
+
+        for portal_mut in map.portals_mut() {
+            let old_position = *portal_mut.local_position();
+            let new_position = Position::new(
+                self.scale_axis(old_position.x(), size.width()),
+                self.scale_axis(old_position.y(), size.height()),
+            );
+            *portal_mut.local_position_mut() = new_position;
+
+            let target = portal_mut.target();
+            move_portal_label(map_id, (old_position, target), (new_position, target));
+        }
+
+        let mut portal_positions = Vec::new();
+        for portal in map.portals() {
+            portal_positions.push(*portal.local_position());
+        }
+        for position in portal_positions {
+            map.tile_type_at_local_set(position, TileType::Portal);
+        }
+
+        for sub_map in map.sub_maps_mut() {
+            let old_position = *sub_map.local_position();
+            *sub_map.local_position_mut() =
+                Position::new(old_position.x() * factor, old_position.y() * factor);
+        }
+    }
+}
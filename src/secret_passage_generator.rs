@@ -0,0 +1,104 @@
+// External includes.
+use rand::seq::SliceRandom;
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::dun_gen_rng::dun_gen_rng;
+use crate::geometry::*;
+
+/// A generator for hiding shortcuts in the walls between floors.
+///
+/// The `SecretPassageGenerator` chooses walls which have [`TileType`](enum.TileType.html)::Floor on two opposite sides, north and south or east and west, and no floor on the other two sides; that is, walls one tile thick. Up to the provided count of them are chosen at random, and each is marked as a secret door by setting its `"secret_door"` tile attribute to 1, which can be read back with [`MapExt::get_attr()`](trait.MapExt.html#method.get_attr). The tiles stay `TileType::Wall`, so that the doors look like walls until something reveals them. Walls which are already secret doors are not chosen again.
+///
+/// Will create two rooms divided by a wall, and hide two doors in the wall.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(11, 8)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(FillTilesGenerator::new(
+///         Area::new(Position::new(5, 1), Size::new(1, 6)),
+///         TileType::Wall))
+///     .gen_with(SecretPassageGenerator::new(2))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// let mut secret_doors = Vec::new();
+/// map.visit_tiles(|position, _tile_type| {
+///     if map.get_attr(position, "secret_door") == Some(1) {
+///         secret_doors.push(position);
+///     }
+/// });
+/// assert!(secret_doors.len() == 2);
+/// for position in secret_doors {
+///     assert!(map.tile_type_at_local(position) == Some(TileType::Wall));
+///     let is_floor = |offset: Position| map.tile_type_at_local(position + offset) == Some(TileType::Floor);
+///     assert!(is_floor(Position::EAST) && is_floor(Position::WEST));
+///     assert!(!is_floor(Position::NORTH) && !is_floor(Position::SOUTH));
+/// }
+/// ```
+pub struct SecretPassageGenerator<TProvidesCount>
+where
+    TProvidesCount: ProvidesCount + Sized,
+{
+    provides_count: TProvidesCount,
+}
+
+impl<TProvidesCount> SecretPassageGenerator<TProvidesCount>
+where
+    TProvidesCount: ProvidesCount + Sized,
+{
+    /// Creates a new generator for hiding the provided count of secret doors.
+    pub fn new(provides_count: TProvidesCount) -> Self {
+        Self { provides_count }
+    }
+}
+
+impl<TProvidesCount> DoesDunGen for SecretPassageGenerator<TProvidesCount>
+where
+    TProvidesCount: ProvidesCount + Sized,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let mut candidates = Vec::new();
+        map.visit_tiles(|position, tile_type| {
+            if tile_type != TileType::Wall || map.get_attr(position, "secret_door").is_some() {
+                return;
+            }
+
+            let is_floor = |offset: Position| {
+                map.tile_type_at_local(position + offset) == Some(TileType::Floor)
+            };
+            let north_south = is_floor(Position::NORTH) && is_floor(Position::SOUTH);
+            let east_west = is_floor(Position::EAST) && is_floor(Position::WEST);
+            let north_or_south = is_floor(Position::NORTH) || is_floor(Position::SOUTH);
+            let east_or_west = is_floor(Position::EAST) || is_floor(Position::WEST);
+            if (north_south && !east_or_west) || (east_west && !north_or_south) {
+                candidates.push(position);
+            }
+        });
+
+        let count = self.provides_count.provide_count();
+        let mut rng = dun_gen_rng();
+        let chosen = candidates
+            .choose_multiple(&mut rng, count)
+            .copied()
+            .collect::<Vec<Position>>();
+        for position in chosen {
+            map.set_attr(position, "secret_door", 1);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use crate::geometry::*;
+
+/// Returns how a shape covers the given local position, for shapes which are not rectangular; the position is on the shape's edge if any of its 8 neighbours is outside of the shape.
+pub(crate) fn edge_containment<TShape>(shape: &TShape, position: Position) -> Containment
+where
+    TShape: IntersectsLocalPosition + ?Sized,
+{
+    if !shape.intersects_local_position(position) {
+        return Containment::Disjoint;
+    }
+
+    let on_edge = [
+        Position::NORTH,
+        Position::NORTH + Position::EAST,
+        Position::EAST,
+        Position::SOUTH + Position::EAST,
+        Position::SOUTH,
+        Position::SOUTH + Position::WEST,
+        Position::WEST,
+        Position::NORTH + Position::WEST,
+    ]
+    .iter()
+    .any(|direction| !shape.intersects_local_position(position + *direction));
+    if on_edge {
+        Containment::Intersects
+    } else {
+        Containment::Contains
+    }
+}
@@ -0,0 +1,194 @@
+// External includes.
+use rand::seq::SliceRandom;
+
+// Standard includes.
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::RwLock;
+
+// Internal includes.
+use super::*;
+use crate::dun_gen_rng::dun_gen_rng;
+use crate::geometry::*;
+
+/// A generator for carving a perfect maze, and recording its solution.
+///
+/// The `SolutionMazeGenerator` fills the map's [`Size`](geometry/struct.Size.html) with [`TileType`](enum.TileType.html)::Wall, and then carves a perfect maze of `TileType::Floor` through it; every floor tile is connected, and there is exactly one path between any two floor tiles. The maze is carved on the odd local [`Position`](geometry/struct.Position.html)s, so the outer edge of the map is always wall. Maps less than 3 tiles wide or high are left untouched.
+///
+/// The entrance of the maze is the local position (1, 1), and the exit is the bottom-right-most carved tile. The path from the entrance to the exit is stored per [`MapId`](type.MapId.html), and can be retrieved after generation through [`SolutionMazeGenerator::solution()`](#method.solution).
+///
+/// Will create a maze 21 tiles wide by 15 tiles high, and check that there is exactly one simple path between its entrance and exit.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let maze_generator = SolutionMazeGenerator::new();
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(21, 15)))
+///     .build();
+/// maze_generator.dun_gen_map(map_id);
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// let solution = maze_generator.solution(map_id);
+/// let entrance = solution[0];
+/// let exit = solution[solution.len() - 1];
+/// assert!(entrance == Position::new(1, 1));
+/// assert!(exit == Position::new(19, 13));
+///
+/// // The solution is a walk over floor tiles, one step at a time.
+/// for step in solution.windows(2) {
+///     assert!(map.tile_type_at_local(step[0]) == Some(TileType::Floor));
+///     let difference = step[1] - step[0];
+///     assert!(difference.x().abs() + difference.y().abs() == 1);
+/// }
+///
+/// // The floor tiles form a tree (connected, with one fewer connection than tiles), so there is
+/// // exactly one simple path between the entrance and exit.
+/// let mut floor_count = 0;
+/// let mut connection_count = 0;
+/// map.visit_tiles(|position, tile_type| {
+///     if tile_type == TileType::Floor {
+///         floor_count += 1;
+///         for neighbour in &[position + Position::EAST, position + Position::SOUTH] {
+///             if map.tile_type_at_local(*neighbour) == Some(TileType::Floor) {
+///                 connection_count += 1;
+///             }
+///         }
+///     }
+/// });
+/// assert!(connection_count == floor_count - 1);
+/// assert!(map.count_tiles(TileType::Floor) == floor_count);
+/// ```
+pub struct SolutionMazeGenerator {
+    solutions: RwLock<HashMap<MapId, Vec<Position>>>,
+}
+
+impl SolutionMazeGenerator {
+    /// Creates a new generator for carving perfect mazes.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            solutions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the path from the entrance to the exit of the maze generated for the given `MapId`, including both the entrance and the exit. Returns an empty `Vec` if no maze was generated for the map.
+    pub fn solution(&self, map_id: MapId) -> Vec<Position> {
+        self.solutions
+            .read()
+            .unwrap()
+            .get(&map_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl DoesDunGen for SolutionMazeGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let size = *map.size();
+        if size.width() < 3 || size.height() < 3 {
+            return;
+        }
+
+        for y in 0..size.height() {
+            for x in 0..size.width() {
+                map.tile_type_at_local_set(Position::new(x as i32, y as i32), TileType::Wall);
+            }
+        }
+
+        // Cells are on odd positions; the walls between them are on even positions. The exit is
+        // the last odd position which is not on the edge of the map.
+        let entrance = Position::new(1, 1);
+        let exit = Position::new(
+            (size.width() as i32 - 3) | 1,
+            (size.height() as i32 - 3) | 1,
+        );
+        let is_cell = |position: Position| {
+            position.x() >= 1
+                && position.y() >= 1
+                && position.x() <= exit.x()
+                && position.y() <= exit.y()
+        };
+
+        let mut rng = dun_gen_rng();
+        let mut visited = HashSet::new();
+        let mut stack = vec![entrance];
+        visited.insert(entrance);
+        map.tile_type_at_local_set(entrance, TileType::Floor);
+        while let Some(&cell) = stack.last() {
+            let mut directions = [
+                Position::NORTH,
+                Position::EAST,
+                Position::SOUTH,
+                Position::WEST,
+            ];
+            directions.shuffle(&mut rng);
+            let next = directions.iter().find_map(|direction| {
+                let next_cell = cell + *direction + *direction;
+                if is_cell(next_cell) && !visited.contains(&next_cell) {
+                    Some((cell + *direction, next_cell))
+                } else {
+                    None
+                }
+            });
+
+            match next {
+                Some((between, next_cell)) => {
+                    map.tile_type_at_local_set(between, TileType::Floor);
+                    map.tile_type_at_local_set(next_cell, TileType::Floor);
+                    visited.insert(next_cell);
+                    stack.push(next_cell);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+
+        let mut came_from = HashMap::new();
+        let mut open = VecDeque::new();
+        came_from.insert(entrance, entrance);
+        open.push_back(entrance);
+        while let Some(position) = open.pop_front() {
+            if position == exit {
+                break;
+            }
+
+            for direction in [
+                Position::NORTH,
+                Position::EAST,
+                Position::SOUTH,
+                Position::WEST,
+            ]
+            .iter()
+            {
+                let neighbour = position + *direction;
+                if map.tile_type_at_local(neighbour) == Some(TileType::Floor)
+                    && !came_from.contains_key(&neighbour)
+                {
+                    came_from.insert(neighbour, position);
+                    open.push_back(neighbour);
+                }
+            }
+        }
+
+        let mut solution = vec![exit];
+        let mut position = exit;
+        while position != entrance {
+            position = came_from[&position];
+            solution.push(position);
+        }
+        solution.reverse();
+
+        self.solutions.write().unwrap().insert(map_id, solution);
+    }
+}
@@ -0,0 +1,47 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::default_tile_map::{DefaultTileMap, DEFAULT_TILES};
+
+/// Additional constructors for a [`SparseMap`](struct.SparseMap.html).
+pub trait SparseMapExt {
+    /// Creates a new map, as `SparseMap::new()` does, which returns the given [`TileType`](enum.TileType.html) for every local position within its [`Size`](geometry/struct.Size.html) which has no tile, instead of `None`.
+    ///
+    /// Useful for carving, where the map should start out solid. Local positions outside of the map's size still return `None`.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id = SparseMap::with_default_tile(TileType::Wall);
+    ///
+    /// let maps = MAPS.read();
+    /// let mut map = maps[map_id].write();
+    /// map.resize(Size::new(5, 5));
+    ///
+    /// assert!(map.tile_type_at_local(Position::new(2, 2)) == Some(TileType::Wall));
+    /// map.tile_type_at_local_set(Position::new(2, 2), TileType::Floor);
+    /// assert!(map.tile_type_at_local(Position::new(2, 2)) == Some(TileType::Floor));
+    /// assert!(map.tile_type_at_local(Position::new(2, 3)) == Some(TileType::Wall));
+    /// assert!(map.tile_type_at_local(Position::new(5, 5)) == None);
+    ///
+    /// assert!(map.count_tiles(TileType::Wall) == (5 * 5) - 1);
+    /// ```
+    fn with_default_tile(default_tile: TileType) -> MapId;
+}
+
+impl SparseMapExt for SparseMap {
+    fn with_default_tile(default_tile: TileType) -> MapId {
+        let map_id = SparseMap::new();
+        {
+            let maps = &MAPS.read();
+            let map = &mut maps[map_id].write();
+            let default_tile_map = DefaultTileMap::new(map.box_clone(), default_tile);
+            **map = Box::new(default_tile_map);
+        }
+        DEFAULT_TILES.write().unwrap().insert(map_id, default_tile);
+
+        map_id
+    }
+}
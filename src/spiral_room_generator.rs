@@ -0,0 +1,117 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for carving a rectangular spiral corridor into a map.
+///
+/// The `SpiralRoomGenerator` fills the map's [`Size`](geometry/struct.Size.html) with [`TileType`](enum.TileType.html)::Wall, and then carves a corridor of `TileType::Floor`, 1 tile wide, spiralling clockwise outward from the middle of the map; east, south, west, north, and around again. The given arm spacing is the thickness of the walls left between neighbouring arms of the spiral. The spiral stops when it reaches the outermost tiles of the map, which are left as walls. Maps smaller than 3 tiles wide or 3 tiles high are left as-is.
+///
+/// Will create a map 21 tiles wide by 21 tiles high, and carve a spiral with 1 tile of wall between its arms.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(21, 21)))
+///     .gen_with(SpiralRoomGenerator::new(1))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// let center = Position::new(10, 10);
+/// assert!(map.tile_type_at_local(center) == Some(TileType::Floor));
+///
+/// // The arms above the middle alternate with walls.
+/// for y in 2..=10 {
+///     let tile_type = map.tile_type_at_local(Position::new(10, y));
+///     if (10 - y) % 2 == 0 {
+///         assert!(tile_type == Some(TileType::Floor));
+///     } else {
+///         assert!(tile_type == Some(TileType::Wall));
+///     }
+/// }
+/// assert!(map.tile_type_at_local(Position::new(10, 0)) == Some(TileType::Wall));
+///
+/// // The spiral is a single corridor.
+/// let mut reached = std::collections::HashSet::new();
+/// let mut open = vec![center];
+/// reached.insert(center);
+/// while let Some(position) = open.pop() {
+///     for neighbor in map.walkable_neighbors(position) {
+///         if reached.insert(neighbor) {
+///             open.push(neighbor);
+///         }
+///     }
+/// }
+/// assert!(reached.len() == map.count_tiles(TileType::Floor));
+/// assert!(map.count_tiles(TileType::Floor) + map.count_tiles(TileType::Wall) == 21 * 21);
+/// ```
+pub struct SpiralRoomGenerator {
+    arm_spacing: u32,
+}
+
+impl SpiralRoomGenerator {
+    /// Creates a new generator for carving a spiral with the given thickness of wall between its arms.
+    pub fn new(arm_spacing: u32) -> Self {
+        Self { arm_spacing }
+    }
+}
+
+impl DoesDunGen for SpiralRoomGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let width = map.size().width() as i32;
+        let height = map.size().height() as i32;
+        if width < 3 || height < 3 {
+            return;
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                map.tile_type_at_local_set(Position::new(x, y), TileType::Wall);
+            }
+        }
+
+        let is_inside = |position: Position| {
+            position.x() > 0
+                && position.y() > 0
+                && position.x() < width - 1
+                && position.y() < height - 1
+        };
+        let directions = [
+            Position::EAST,
+            Position::SOUTH,
+            Position::WEST,
+            Position::NORTH,
+        ];
+        let step = self.arm_spacing as i32 + 1;
+
+        let mut position = Position::new(width / 2, height / 2);
+        map.tile_type_at_local_set(position, TileType::Floor);
+        for segment in 0.. {
+            let direction = directions[segment % 4];
+            let length = (segment as i32 / 2 + 1) * step;
+            for _ in 0..length {
+                let next = position + direction;
+                if !is_inside(next) {
+                    return;
+                }
+
+                position = next;
+                map.tile_type_at_local_set(position, TileType::Floor);
+            }
+        }
+    }
+}
@@ -76,6 +76,52 @@ use crate::geometry::*;
 ///     assert!(sub_map_count >= 4 && sub_map_count <= 9);
 /// })
 /// ```
+///
+/// With [`SubMapGenerator::with_no_overlap()`](#method.with_no_overlap), sub-maps which would overlap a sub-map already on the map are generated again, so that no two sub-maps overlap.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(60, 60)))
+///     .gen_with(SubMapGenerator::<fn(Position, MapId) -> bool>::new(
+///         &[SubMapGeneratorSet::new(
+///             &12,
+///             &Area::new(Position::new(0, 0), Size::new(54, 54)),
+///             Some(Box::new(SparseMap::new)),
+///             Some(&[&EmptyRoomGenerator::new(SizeRange::new(
+///                 Size::new(3, 3),
+///                 Size::new(6, 6),
+///             ))]),
+///             None,
+///         )],
+///         None,
+///         None,
+///         None,
+///     ).with_no_overlap(true))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// let areas = map
+///     .sub_maps()
+///     .into_iter()
+///     .map(|sub_map| {
+///         let mut area = *maps[sub_map.value()].read().area();
+///         *area.position_mut() = *area.position() + *sub_map.local_position();
+///         area
+///     })
+///     .collect::<Vec<Area>>();
+/// assert!(areas.len() == 12);
+/// for (index, area) in areas.iter().enumerate() {
+///     for other in &areas[(index + 1)..] {
+///         let overlaps_x = area.left() <= other.right() && other.left() <= area.right();
+///         let overlaps_y = area.top() <= other.bottom() && other.top() <= area.bottom();
+///         assert!(!(overlaps_x && overlaps_y));
+///     }
+/// }
+/// ```
 pub struct SubMapGenerator<'a, TValidityCheck>
 where
     TValidityCheck: Fn(Position, MapId) -> bool,
@@ -84,6 +130,8 @@ where
     fallback_map_provider: Option<Box<dyn Fn() -> MapId>>,
     global_sub_map_generators: Option<&'a [&'a dyn DoesDunGen]>,
     validity_check: Option<TValidityCheck>,
+    no_overlap: bool,
+    max_tries: usize,
 }
 
 /// Contains information about generating a sub-map for SubMapGenerator.
@@ -130,8 +178,82 @@ where
             fallback_map_provider,
             global_sub_map_generators,
             validity_check,
+            no_overlap: false,
+            max_tries: 100,
         }
     }
+
+    /// Sets whether a sub-map whose [`Area`](geometry/struct.Area.html) intersects the area of a sub-map already on the map is rejected, and generated again. By default, sub-maps may overlap.
+    ///
+    /// A sub-map is generated up to `max_tries` times, set with [`SubMapGenerator::with_max_tries()`](#method.with_max_tries), before the generator gives up on it; the rest of the sub-maps in its [`SubMapGeneratorSet`](struct.SubMapGeneratorSet.html) are then skipped, as there is likely no room left for them.
+    ///
+    /// Will try to place 20 sub-maps where only a few fit, and stop once there is no room left.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(12, 12)))
+    ///     .gen_with(SubMapGenerator::<fn(Position, MapId) -> bool>::new(
+    ///         &[SubMapGeneratorSet::new(
+    ///             &20,
+    ///             &Area::new(Position::new(0, 0), Size::new(7, 7)),
+    ///             Some(Box::new(SparseMap::new)),
+    ///             Some(&[&EmptyRoomGenerator::new(Size::new(5, 5))]),
+    ///             None,
+    ///         )],
+    ///         None,
+    ///         None,
+    ///         None,
+    ///     ).with_no_overlap(true).with_max_tries(50))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    /// assert!(map.sub_map_count() >= 1 && map.sub_map_count() <= 4);
+    /// ```
+    pub fn with_no_overlap(mut self, no_overlap: bool) -> Self {
+        self.no_overlap = no_overlap;
+        self
+    }
+
+    /// Sets how many times a sub-map which overlaps another is generated again, with [`SubMapGenerator::with_no_overlap()`](#method.with_no_overlap), before the generator gives up on it. The default is 100.
+    pub fn with_max_tries(mut self, max_tries: usize) -> Self {
+        self.max_tries = max_tries;
+        self
+    }
+
+    fn overlaps_placed_sub_map(map_id: MapId, position: Position, new_map_id: MapId) -> bool {
+        let maps = &MAPS.read();
+        let placed_area = |local_position: Position, sub_map_id: MapId| {
+            let mut area = *maps[sub_map_id].read().area();
+            *area.position_mut() = *area.position() + local_position;
+            area
+        };
+
+        let area = placed_area(position, new_map_id);
+        let map = &maps[map_id].read();
+        map.sub_maps().into_iter().any(|sub_map| {
+            let other = placed_area(*sub_map.local_position(), sub_map.value());
+            Self::areas_intersect(&area, &other)
+        })
+    }
+
+    fn areas_intersect(area: &Area, other: &Area) -> bool {
+        let (left, top) = (area.position().x(), area.position().y());
+        let (right, bottom) = (
+            left + area.size().width() as i32,
+            top + area.size().height() as i32,
+        );
+        let (other_left, other_top) = (other.position().x(), other.position().y());
+        let (other_right, other_bottom) = (
+            other_left + other.size().width() as i32,
+            other_top + other.size().height() as i32,
+        );
+
+        // The right and bottom are exclusive, so areas which only touch do not intersect.
+        left < other_right && other_left < right && top < other_bottom && other_top < bottom
+    }
 }
 
 impl<'a, TValidityCheck> DoesDunGen for SubMapGenerator<'a, TValidityCheck>
@@ -160,8 +282,9 @@ where
             );
 
             let count = provides_count.provide_count();
-            for _ in 0..count {
+            'sub_maps: for _ in 0..count {
                 let (mut position, mut new_map_id);
+                let mut tries = 0;
                 loop {
                     position = provides_position.provide_position();
                     new_map_id = if let Some(map_provider) = map_provider {
@@ -186,13 +309,24 @@ where
                         }
                     }
 
+                    if self.no_overlap
+                        && Self::overlaps_placed_sub_map(map_id, position, new_map_id)
+                    {
+                        free_map(new_map_id);
+                        tries += 1;
+                        if tries >= self.max_tries {
+                            break 'sub_maps;
+                        }
+                        continue;
+                    }
+
                     if validity_check.is_none() && self.validity_check.is_none() {
                         break;
                     }
 
                     if let Some(validity_check) = validity_check {
                         if !validity_check(position, new_map_id) {
-                            invalidate_map(new_map_id)
+                            free_map(new_map_id)
                         } else {
                             break;
                         }
@@ -200,7 +334,7 @@ where
 
                     if let Some(validity_check) = &self.validity_check {
                         if !validity_check(position, new_map_id) {
-                            invalidate_map(new_map_id)
+                            free_map(new_map_id)
                         } else {
                             break;
                         }
@@ -0,0 +1,112 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// The axis across which a [`SymmetryGenerator`](struct.SymmetryGenerator.html) mirrors a map.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum MirrorAxis {
+    /// Mirrors the left half of the map onto the right half.
+    Vertical,
+    /// Mirrors the top half of the map onto the bottom half.
+    Horizontal,
+}
+
+/// A generator for making a map bilaterally symmetric.
+///
+/// The `SymmetryGenerator` copies the tiles of one half of the map onto the other half, mirrored across the given [`MirrorAxis`](enum.MirrorAxis.html). If the map has an odd width (or height), the central column (or row) is left as-is.
+///
+/// A `TileType::Portal` tile belongs to a [`Portal`](struct.Portal.html), which is not copied; so [`TileType`](enum.TileType.html)::Portal tiles are neither mirrored, nor overwritten, and the tile opposite a portal is left as-is.
+///
+/// Will create a walled map with a `Size` of 11 tiles wide by 7 tiles high, with randomly-placed portals, and then mirror its left half onto its right half.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(11, 7)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(EdgePortalsGenerator::new(4, Box::new(|| SparseMap::new())))
+///     .gen_with(SymmetryGenerator::new(MirrorAxis::Vertical))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// let width = map.size().width() as i32;
+/// for y in 0..map.size().height() as i32 {
+///     for x in 0..width {
+///         let tile_type = map.tile_type_at_local(Position::new(x, y));
+///         let mirrored_tile_type = map.tile_type_at_local(Position::new(width - 1 - x, y));
+///         if tile_type != Some(TileType::Portal) && mirrored_tile_type != Some(TileType::Portal) {
+///             assert!(tile_type == mirrored_tile_type);
+///         }
+///     }
+/// }
+///
+/// // Every portal keeps its tile, and no tile is left without its portal.
+/// assert!(map.portal_count() == 4);
+/// assert!(map.count_tiles(TileType::Portal) == 4);
+/// for portal in map.portals() {
+///     assert!(map.tile_type_at_local(*portal.local_position()) == Some(TileType::Portal));
+/// }
+/// ```
+pub struct SymmetryGenerator {
+    axis: MirrorAxis,
+}
+
+impl SymmetryGenerator {
+    /// Creates a new generator for mirroring a map across the given axis.
+    pub fn new(axis: MirrorAxis) -> Self {
+        Self { axis }
+    }
+}
+
+impl DoesDunGen for SymmetryGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let width = map.size().width() as i32;
+        let height = map.size().height() as i32;
+        let (half_width, half_height) = match self.axis {
+            MirrorAxis::Vertical => (width / 2, height),
+            MirrorAxis::Horizontal => (width, height / 2),
+        };
+
+        for y in 0..half_height {
+            for x in 0..half_width {
+                let source = Position::new(x, y);
+                let destination = match self.axis {
+                    MirrorAxis::Vertical => Position::new(width - 1 - x, y),
+                    MirrorAxis::Horizontal => Position::new(x, height - 1 - y),
+                };
+
+                if map.tile_type_at_local(source) == Some(TileType::Portal)
+                    || map.tile_type_at_local(destination) == Some(TileType::Portal)
+                {
+                    continue;
+                }
+
+                match map.tile_type_at_local(source) {
+                    Some(tile_type) => {
+                        map.tile_type_at_local_set(destination, tile_type);
+                    }
+                    None => {
+                        if map.tile_type_at_local(destination).is_some() {
+                            map.tile_type_at_local_set(destination, TileType::Void);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+// External includes.
+
+// Standard includes.
+use std::collections::HashSet;
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for carving a wide, L-shaped corridor between two positions, with a rounded inner corner.
+///
+/// The `ThickCorridorGenerator` carves [`TileType`](enum.TileType.html)::Floor along a path which runs horizontally from the start position to the end position's column, and then vertically to the end position. The corridor is the given number of tiles wide, centred on the path; an even width leaves the extra tile to the south of a horizontal stretch, and to the east of a vertical stretch. The outer corner of the bend is square. For corridors at least 2 tiles wide, the single tile in the inner corner of the bend is also carved, rounding off the sharp notch so that the hall turns more naturally. A width of 0 carves nothing, and tiles which would fall at negative local positions are skipped.
+///
+/// Will carve a corridor 3 tiles wide east, and then south, through a solid map.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(16, 14), TileType::Wall))
+///     .gen_with(ThickCorridorGenerator::new(Position::new(2, 2), Position::new(12, 10), 3))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// // The horizontal stretch, and the vertical stretch.
+/// for x in 1..=13 {
+///     for y in 1..=3 {
+///         assert!(map.tile_type_at_local(Position::new(x, y)) == Some(TileType::Floor));
+///     }
+/// }
+/// for y in 1..=11 {
+///     for x in 11..=13 {
+///         assert!(map.tile_type_at_local(Position::new(x, y)) == Some(TileType::Floor));
+///     }
+/// }
+/// // The inner corner is rounded, without widening the rest of the corridor.
+/// assert!(map.tile_type_at_local(Position::new(10, 4)) == Some(TileType::Floor));
+/// assert!(map.tile_type_at_local(Position::new(9, 4)) == Some(TileType::Wall));
+/// assert!(map.tile_type_at_local(Position::new(10, 5)) == Some(TileType::Wall));
+/// assert!(map.count_tiles(TileType::Floor) == (13 * 3) + (8 * 3) + 1);
+/// ```
+pub struct ThickCorridorGenerator {
+    from: Position,
+    to: Position,
+    width: u32,
+}
+
+impl ThickCorridorGenerator {
+    /// Creates a new generator for carving a corridor of the given width between two local positions.
+    pub fn new(from: Position, to: Position, width: u32) -> Self {
+        Self { from, to, width }
+    }
+
+    fn path(&self) -> Vec<Position> {
+        let mut output = vec![self.from];
+        let mut position = self.from;
+        let step_x = (self.to.x() - self.from.x()).signum();
+        let step_y = (self.to.y() - self.from.y()).signum();
+        while position.x() != self.to.x() {
+            position = Position::new(position.x() + step_x, position.y());
+            output.push(position);
+        }
+        while position.y() != self.to.y() {
+            position = Position::new(position.x(), position.y() + step_y);
+            output.push(position);
+        }
+
+        output
+    }
+}
+
+impl DoesDunGen for ThickCorridorGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        if self.width == 0 {
+            return;
+        }
+
+        let low = -((self.width as i32 - 1) / 2);
+        let high = self.width as i32 / 2;
+        let mut carved = HashSet::new();
+        for position in self.path() {
+            for y in low..=high {
+                for x in low..=high {
+                    carved.insert(position + Position::new(x, y));
+                }
+            }
+        }
+
+        // The inner corner of the bend is the only tile outside of the corridor with corridor
+        // tiles on two sides at right angles to each other.
+        let mut rounded = Vec::new();
+        if self.width > 1 {
+            for position in &carved {
+                for offset in Connectivity::Four.neighbor_offsets() {
+                    let candidate = *position + offset;
+                    if carved.contains(&candidate) {
+                        continue;
+                    }
+
+                    let vertical = carved.contains(&(candidate + Position::NORTH))
+                        || carved.contains(&(candidate + Position::SOUTH));
+                    let horizontal = carved.contains(&(candidate + Position::EAST))
+                        || carved.contains(&(candidate + Position::WEST));
+                    if vertical && horizontal {
+                        rounded.push(candidate);
+                    }
+                }
+            }
+        }
+
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+        map.set_tiles(
+            carved
+                .into_iter()
+                .chain(rounded)
+                .filter(|position| position.x() >= 0 && position.y() >= 0)
+                .map(|position| (position, TileType::Floor)),
+        );
+    }
+}
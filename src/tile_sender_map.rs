@@ -0,0 +1,229 @@
+// External includes.
+
+// Standard includes.
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// The `Sender` shared by a `TileSenderMap` and its clones; taking the `Sender` out detaches them all.
+pub(crate) type TileSenderHandle = Arc<Mutex<Option<Sender<(Position, TileType)>>>>;
+
+/// Wraps a [`Map`](trait.Map.html), sending every tile written through `tile_type_at_local_set` over a channel.
+///
+/// Installed by [`DunGen::with_tile_sender()`](struct.DunGen.html#method.with_tile_sender), and removed by [`DunGen::without_tile_sender()`](struct.DunGen.html#method.without_tile_sender); every other method forwards to the wrapped map. Once detached, the wrapper sends nothing, and cloning it clones the wrapped map, only.
+#[derive(Clone)]
+pub(crate) struct TileSenderMap {
+    map: Box<dyn Map>,
+    tile_sender: TileSenderHandle,
+}
+
+impl TileSenderMap {
+    pub(crate) fn new(map: Box<dyn Map>, tile_sender: TileSenderHandle) -> Self {
+        Self { map, tile_sender }
+    }
+
+    fn is_detached(&self) -> bool {
+        match self.tile_sender.lock() {
+            Ok(tile_sender) => tile_sender.is_none(),
+            Err(_) => true,
+        }
+    }
+
+    fn send(&self, position: Position, tile_type: TileType) {
+        // If the receiver has been dropped, there is no-one to tell; generation carries on.
+        if let Ok(tile_sender) = self.tile_sender.lock() {
+            if let Some(tile_sender) = tile_sender.as_ref() {
+                let _ = tile_sender.send((position, tile_type));
+            }
+        }
+    }
+}
+
+impl ContainsLocalPosition for TileSenderMap {
+    fn contains_local_position(&self, position: Position) -> Containment {
+        self.map.contains_local_position(position)
+    }
+}
+
+impl ContainsPosition for TileSenderMap {}
+
+impl HasArea for TileSenderMap {
+    fn area(&self) -> &Area {
+        self.map.area()
+    }
+
+    fn area_mut(&mut self) -> &mut Area {
+        self.map.area_mut()
+    }
+}
+
+impl HasHeight for TileSenderMap {
+    fn height(&self) -> Length {
+        self.map.height()
+    }
+
+    fn height_mut(&mut self) -> &mut Length {
+        self.map.height_mut()
+    }
+}
+
+impl HasPosition for TileSenderMap {
+    fn position(&self) -> &Position {
+        self.map.position()
+    }
+
+    fn position_mut(&mut self) -> &mut Position {
+        self.map.position_mut()
+    }
+}
+
+impl HasSize for TileSenderMap {
+    fn size(&self) -> &Size {
+        self.map.size()
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        self.map.size_mut()
+    }
+}
+
+impl HasWidth for TileSenderMap {
+    fn width(&self) -> Length {
+        self.map.width()
+    }
+
+    fn width_mut(&mut self) -> &mut Length {
+        self.map.width_mut()
+    }
+}
+
+impl IntersectsLocalPosition for TileSenderMap {
+    fn intersects_local_position(&self, position: Position) -> bool {
+        self.map.intersects_local_position(position)
+    }
+}
+
+impl IntersectsPosition for TileSenderMap {}
+
+impl Map for TileSenderMap {
+    fn box_clone(&self) -> Box<dyn Map> {
+        if self.is_detached() {
+            self.map.box_clone()
+        } else {
+            Box::new((*self).clone())
+        }
+    }
+
+    fn is_local_position_valid(&self, position: Position) -> bool {
+        self.map.is_local_position_valid(position)
+    }
+
+    fn map_id(&self) -> MapId {
+        self.map.map_id()
+    }
+
+    fn rotate(&mut self, rotation: CardinalRotation) {
+        self.map.rotate(rotation)
+    }
+
+    fn tile_type_at_local(&self, pos: Position) -> Option<TileType> {
+        self.map.tile_type_at_local(pos)
+    }
+
+    fn tile_type_at_local_mut(&mut self, pos: Position) -> Option<&mut TileType> {
+        self.map.tile_type_at_local_mut(pos)
+    }
+
+    fn tile_type_at_local_set(&mut self, pos: Position, tile_type: TileType) -> Option<TileType> {
+        let output = self.map.tile_type_at_local_set(pos, tile_type);
+        self.send(pos, tile_type);
+
+        output
+    }
+
+    fn tile_type_at_local_sort_by(
+        &self,
+        pos: Position,
+        sort_best: &dyn Fn(&Option<TileType>, &Option<TileType>) -> std::cmp::Ordering,
+    ) -> Option<TileType> {
+        self.map.tile_type_at_local_sort_by(pos, sort_best)
+    }
+}
+
+impl Placed for TileSenderMap {}
+
+impl PlacedObject for TileSenderMap {}
+
+impl PortalCollection for TileSenderMap {
+    fn add_portal(
+        &mut self,
+        local_position: Position,
+        portal_to_map_facing: CardinalDirection,
+        portal_to_map_position: Position,
+        target: MapId,
+    ) {
+        self.map.add_portal(
+            local_position,
+            portal_to_map_facing,
+            portal_to_map_position,
+            target,
+        );
+        // The wrapped map sets the portal's tile itself, bypassing this wrapper.
+        self.send(local_position, TileType::Portal);
+    }
+
+    fn get_portal_at(&self, index: usize) -> Option<&Portal> {
+        self.map.get_portal_at(index)
+    }
+
+    fn get_portal_at_mut(&mut self, index: usize) -> Option<&mut Portal> {
+        self.map.get_portal_at_mut(index)
+    }
+
+    fn portal_count(&self) -> usize {
+        self.map.portal_count()
+    }
+
+    fn portals(&self) -> Portals<'_> {
+        self.map.portals()
+    }
+
+    fn portals_mut(&mut self) -> PortalsMut<'_> {
+        self.map.portals_mut()
+    }
+}
+
+impl Shape for TileSenderMap {
+    fn box_shape_clone(&self) -> Box<dyn Shape> {
+        Box::new((*self).clone())
+    }
+}
+
+impl SubMapCollection for TileSenderMap {
+    fn add_sub_map(&mut self, local_position: Position, target: MapId) {
+        self.map.add_sub_map(local_position, target)
+    }
+
+    fn get_sub_map_at(&self, index: usize) -> Option<&SubMap> {
+        self.map.get_sub_map_at(index)
+    }
+
+    fn get_sub_map_at_mut(&mut self, index: usize) -> Option<&mut SubMap> {
+        self.map.get_sub_map_at_mut(index)
+    }
+
+    fn sub_map_count(&self) -> usize {
+        self.map.sub_map_count()
+    }
+
+    fn sub_maps(&self) -> SubMaps<'_> {
+        self.map.sub_maps()
+    }
+
+    fn sub_maps_mut(&mut self) -> SubMapsMut<'_> {
+        self.map.sub_maps_mut()
+    }
+}
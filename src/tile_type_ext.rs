@@ -0,0 +1,85 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+
+/// Helper methods for a [`TileType`](enum.TileType.html); implemented for `TileType`.
+///
+/// These classify each `TileType` for movement and sight, so that pathfinding and field-of-view code does not need to list the variants itself, and convert each `TileType` to and from the character it is drawn as in text.
+///
+/// | `TileType` | Walkable | Opaque | Character |
+/// |------------|----------|--------|-----------|
+/// | `Void`     | No       | Yes    | `' '`     |
+/// | `Floor`    | Yes      | No     | `'.'`     |
+/// | `Wall`     | No       | Yes    | `'#'`     |
+/// | `Portal`   | Yes      | No     | `'+'`     |
+/// ```
+/// # use dungen_minion::*;
+/// assert!(!TileType::Void.is_walkable());
+/// assert!(TileType::Floor.is_walkable());
+/// assert!(!TileType::Wall.is_walkable());
+/// assert!(TileType::Portal.is_walkable());
+///
+/// assert!(TileType::Void.is_opaque());
+/// assert!(!TileType::Floor.is_opaque());
+/// assert!(TileType::Wall.is_opaque());
+/// assert!(!TileType::Portal.is_opaque());
+///
+/// for tile_type in &[TileType::Void, TileType::Floor, TileType::Wall, TileType::Portal] {
+///     assert!(TileType::from_char(tile_type.to_char()) == Some(*tile_type));
+/// }
+/// assert!(TileType::Wall.to_char() == '#');
+/// assert!(TileType::from_char('x') == None);
+/// ```
+pub trait TileTypeExt {
+    /// Returns `true` if the tile can be walked on.
+    fn is_walkable(&self) -> bool;
+
+    /// Returns `true` if the tile blocks sight.
+    fn is_opaque(&self) -> bool;
+
+    /// Returns the character the tile is drawn as in text.
+    fn to_char(&self) -> char;
+
+    /// Returns the `TileType` drawn as the given character in text, or `None` if no `TileType` is drawn as that character.
+    fn from_char(ch: char) -> Option<Self>
+    where
+        Self: Sized;
+}
+
+impl TileTypeExt for TileType {
+    fn is_walkable(&self) -> bool {
+        match self {
+            TileType::Floor | TileType::Portal => true,
+            TileType::Void | TileType::Wall => false,
+        }
+    }
+
+    fn is_opaque(&self) -> bool {
+        match self {
+            TileType::Void | TileType::Wall => true,
+            TileType::Floor | TileType::Portal => false,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            TileType::Void => ' ',
+            TileType::Floor => '.',
+            TileType::Wall => '#',
+            TileType::Portal => '+',
+        }
+    }
+
+    fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            ' ' => Some(TileType::Void),
+            '.' => Some(TileType::Floor),
+            '#' => Some(TileType::Wall),
+            '+' => Some(TileType::Portal),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// An iterator over the local [`Position`](geometry/struct.Position.html) and `Option<TileType>` of every tile within a map's [`Size`](geometry/struct.Size.html), in row-major order.
+///
+/// Created by [`MapExt::tiles()`](trait.MapExt.html#method.tiles).
+pub struct Tiles<'a, TMap>
+where
+    TMap: Map + ?Sized,
+{
+    map: &'a TMap,
+    size: Size,
+    index: u64,
+}
+
+impl<'a, TMap> Tiles<'a, TMap>
+where
+    TMap: Map + ?Sized,
+{
+    pub(crate) fn new(map: &'a TMap) -> Self {
+        Self {
+            map,
+            size: *map.size(),
+            index: 0,
+        }
+    }
+}
+
+impl<'a, TMap> Iterator for Tiles<'a, TMap>
+where
+    TMap: Map + ?Sized,
+{
+    type Item = (Position, Option<TileType>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let width = self.size.width() as u64;
+        if width == 0 || self.index >= width * self.size.height() as u64 {
+            return None;
+        }
+
+        let position = Position::new((self.index % width) as i32, (self.index / width) as i32);
+        self.index += 1;
+        Some((position, self.map.tile_type_at_local(position)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.size.width() as u64 * self.size.height() as u64)
+            .saturating_sub(self.index) as usize;
+        (remaining, Some(remaining))
+    }
+}
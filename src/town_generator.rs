@@ -0,0 +1,274 @@
+// External includes.
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// Standard includes.
+use std::collections::HashSet;
+
+// Internal includes.
+use super::*;
+use crate::dun_gen_rng::dun_gen_rng;
+use crate::geometry::*;
+
+/// A generator for laying out a town of building footprints on open ground.
+///
+/// The `TownGenerator` fills the map's [`Size`](geometry/struct.Size.html) with [`TileType`](enum.TileType.html)::Floor for the streets, and then places up to the provided count of buildings on it, each sized by an instance of [`ProvidesSize`](geometry/trait.ProvidesSize.html). Each building is a solid rectangle of `TileType::Wall`, with a door on one of its edges; the door is a [`Portal`](struct.Portal.html) facing the street, whose target map is provided by the boxed function, so that building interiors can be generated with, for example, [`TraversePortalsGenerator`](struct.TraversePortalsGenerator.html).
+///
+/// Buildings never overlap or touch, never touch the edge of the map, and are never placed where they would cut the streets off from each other; a building which cannot be placed is skipped. Buildings smaller than 3 tiles wide or high are not placed, as they have no room for a door.
+///
+/// Will create a town 40 tiles wide by 30 tiles high, with up to 8 buildings.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(40, 30)))
+///     .gen_with(TownGenerator::new(
+///         8,
+///         SizeRange::new(Size::new(4, 4), Size::new(8, 6)),
+///         Box::new(|| SparseMap::new()),
+///     ))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(*map.size() == Size::new(40, 30));
+/// assert!(map.portal_count() > 0 && map.portal_count() <= 8);
+///
+/// // Every door leads in from the street.
+/// for portal in map.portals() {
+///     let position = *portal.local_position();
+///     assert!(map.tile_type_at_local(position) == Some(TileType::Portal));
+///     let street = match portal.portal_to_map_facing() {
+///         CardinalDirection::North => position + Position::NORTH,
+///         CardinalDirection::East => position + Position::EAST,
+///         CardinalDirection::South => position + Position::SOUTH,
+///         CardinalDirection::West => position + Position::WEST,
+///     };
+///     assert!(map.tile_type_at_local(street) == Some(TileType::Floor));
+/// }
+///
+/// // The streets are all connected.
+/// let mut streets = Vec::new();
+/// map.visit_tiles(|position, tile_type| {
+///     if tile_type == TileType::Floor {
+///         streets.push(position);
+///     }
+/// });
+/// let mut reached = std::collections::HashSet::new();
+/// let mut open = vec![streets[0]];
+/// reached.insert(streets[0]);
+/// while let Some(position) = open.pop() {
+///     for direction in &[Position::NORTH, Position::EAST, Position::SOUTH, Position::WEST] {
+///         let neighbour = position + *direction;
+///         if map.tile_type_at_local(neighbour) == Some(TileType::Floor) && reached.insert(neighbour) {
+///             open.push(neighbour);
+///         }
+///     }
+/// }
+/// assert!(reached.len() == streets.len());
+///
+/// // Each building is a separate rectangle of walls, with one door.
+/// let mut building_tiles = std::collections::HashSet::new();
+/// for portal in map.portals() {
+///     let mut open = vec![*portal.local_position()];
+///     let mut building = std::collections::HashSet::new();
+///     building.insert(*portal.local_position());
+///     while let Some(position) = open.pop() {
+///         for direction in &[Position::NORTH, Position::EAST, Position::SOUTH, Position::WEST] {
+///             let neighbour = position + *direction;
+///             match map.tile_type_at_local(neighbour) {
+///                 Some(TileType::Wall) | Some(TileType::Portal) => {
+///                     if building.insert(neighbour) {
+///                         open.push(neighbour);
+///                     }
+///                 }
+///                 _ => {}
+///             }
+///         }
+///     }
+///     let doors = building
+///         .iter()
+///         .filter(|position| map.tile_type_at_local(**position) == Some(TileType::Portal))
+///         .count();
+///     assert!(doors == 1);
+///     assert!(building.is_disjoint(&building_tiles));
+///     building_tiles.extend(building);
+/// }
+/// assert!(
+///     building_tiles.len() ==
+///     map.count_tiles(TileType::Wall) + map.count_tiles(TileType::Portal));
+/// ```
+pub struct TownGenerator<TProvidesCount, TProvidesSize>
+where
+    TProvidesCount: ProvidesCount + Sized,
+    TProvidesSize: ProvidesSize + Sized,
+{
+    provides_count: TProvidesCount,
+    provides_size: TProvidesSize,
+    placed_map_box_func: Box<dyn Fn() -> MapId>,
+}
+
+impl<TProvidesCount, TProvidesSize> TownGenerator<TProvidesCount, TProvidesSize>
+where
+    TProvidesCount: ProvidesCount + Sized,
+    TProvidesSize: ProvidesSize + Sized,
+{
+    /// Creates a new generator for laying out a town of building footprints.
+    pub fn new(
+        provides_count: TProvidesCount,
+        provides_size: TProvidesSize,
+        placed_map_box_func: Box<dyn Fn() -> MapId>,
+    ) -> Self {
+        Self {
+            provides_count,
+            provides_size,
+            placed_map_box_func,
+        }
+    }
+
+    fn streets_connected(map: &dyn Map) -> bool {
+        let mut streets = Vec::new();
+        map.visit_tiles(|position, tile_type| {
+            if tile_type == TileType::Floor {
+                streets.push(position);
+            }
+        });
+        let start = match streets.first() {
+            Some(start) => *start,
+            None => return true,
+        };
+
+        let mut reached = HashSet::new();
+        let mut open = vec![start];
+        reached.insert(start);
+        while let Some(position) = open.pop() {
+            for direction in [
+                Position::NORTH,
+                Position::EAST,
+                Position::SOUTH,
+                Position::WEST,
+            ]
+            .iter()
+            {
+                let neighbour = position + *direction;
+                if map.tile_type_at_local(neighbour) == Some(TileType::Floor)
+                    && reached.insert(neighbour)
+                {
+                    open.push(neighbour);
+                }
+            }
+        }
+
+        reached.len() == streets.len()
+    }
+}
+
+impl<TProvidesCount, TProvidesSize> DoesDunGen for TownGenerator<TProvidesCount, TProvidesSize>
+where
+    TProvidesCount: ProvidesCount + Sized,
+    TProvidesSize: ProvidesSize + Sized,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        const PLACEMENT_ATTEMPTS: usize = 100;
+
+        let mut doors = Vec::<(Position, CardinalDirection)>::new();
+        {
+            let maps = &MAPS.read();
+            let map = &mut maps[map_id].write();
+
+            let width = map.size().width() as i32;
+            let height = map.size().height() as i32;
+            for y in 0..height {
+                for x in 0..width {
+                    map.tile_type_at_local_set(Position::new(x, y), TileType::Floor);
+                }
+            }
+
+            let mut rng = dun_gen_rng();
+            // The areas of the buildings, as (left, top, right, bottom), inclusive.
+            let mut buildings = Vec::<(i32, i32, i32, i32)>::new();
+            let count = self.provides_count.provide_count();
+            for _ in 0..count {
+                let size = self.provides_size.provide_size();
+                let (building_width, building_height) = (size.width() as i32, size.height() as i32);
+                // Leave at least one tile of street between the building and the edge of the map.
+                if building_width < 3
+                    || building_height < 3
+                    || building_width > width - 2
+                    || building_height > height - 2
+                {
+                    continue;
+                }
+
+                for _ in 0..PLACEMENT_ATTEMPTS {
+                    let left = rng.gen_range(1, width - building_width);
+                    let top = rng.gen_range(1, height - building_height);
+                    let (right, bottom) = (left + building_width - 1, top + building_height - 1);
+                    // Leave at least one tile of street between buildings.
+                    let touches_building = buildings.iter().any(|other| {
+                        left <= other.2 + 1
+                            && right >= other.0 - 1
+                            && top <= other.3 + 1
+                            && bottom >= other.1 - 1
+                    });
+                    if touches_building {
+                        continue;
+                    }
+
+                    for y in top..=bottom {
+                        for x in left..=right {
+                            map.tile_type_at_local_set(Position::new(x, y), TileType::Wall);
+                        }
+                    }
+                    if !Self::streets_connected(map.as_ref()) {
+                        for y in top..=bottom {
+                            for x in left..=right {
+                                map.tile_type_at_local_set(Position::new(x, y), TileType::Floor);
+                            }
+                        }
+                        continue;
+                    }
+
+                    let mut door_positions = Vec::new();
+                    for x in (left + 1)..right {
+                        door_positions.push((Position::new(x, top), CardinalDirection::North));
+                        door_positions.push((Position::new(x, bottom), CardinalDirection::South));
+                    }
+                    for y in (top + 1)..bottom {
+                        door_positions.push((Position::new(left, y), CardinalDirection::West));
+                        door_positions.push((Position::new(right, y), CardinalDirection::East));
+                    }
+                    doors.push(*door_positions.choose(&mut rng).unwrap());
+                    buildings.push((left, top, right, bottom));
+                    break;
+                }
+            }
+        }
+
+        let doors = doors
+            .iter()
+            .map(|(local_position, portal_to_map_facing)| {
+                (
+                    local_position,
+                    portal_to_map_facing,
+                    (self.placed_map_box_func)(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        {
+            let maps = &MAPS.read();
+            let map = &mut maps[map_id].write();
+            for door in doors {
+                map.add_portal(*door.0, *door.1, Position::zero(), door.2);
+            }
+        }
+    }
+}
@@ -6,6 +6,7 @@ use std::sync::RwLock;
 
 // Internal includes.
 use super::*;
+use crate::current_portal_facing::with_portal_facing;
 
 /// Used to execute a dungeon generator by traversing portals.
 ///
@@ -42,6 +43,49 @@ use super::*;
 /// }
 /// assert!(count == 5);
 ///```
+///
+/// While the inner generator runs on the map at the end of a portal, [`current_portal_facing()`](fn.current_portal_facing.html) returns the portal's facing.
+///
+/// Portals are kept in the order they were added to a map, and are traversed in that order, so that the same chain of generators runs in the same order every time.
+///```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// use std::cell::RefCell;
+///
+/// struct RecordingGenerator<'a> {
+///     visited: &'a RefCell<Vec<MapId>>,
+/// }
+///
+/// impl<'a> DoesDunGen for RecordingGenerator<'a> {
+///     fn dun_gen_map(&self, map_id: MapId) {
+///         self.visited.borrow_mut().push(map_id);
+///     }
+/// }
+///
+/// for _ in 0..10 {
+///     let map_id = SparseMap::new();
+///     let targets = [SparseMap::new(), SparseMap::new(), SparseMap::new()];
+///     {
+///         let maps = MAPS.read();
+///         let mut map = maps[map_id].write();
+///         map.add_portal(Position::new(5, 0), CardinalDirection::South, Position::zero(), targets[0]);
+///         map.add_portal(Position::new(0, 3), CardinalDirection::East, Position::zero(), targets[1]);
+///         map.add_portal(Position::new(2, 0), CardinalDirection::South, Position::zero(), targets[2]);
+///
+///         let mut index = 0;
+///         for portal in map.portals() {
+///             assert!(portal.target() == targets[index]);
+///             index += 1;
+///         }
+///         assert!(index == 3);
+///     }
+///
+///     let visited = RefCell::new(Vec::new());
+///     DunGen::new(map_id)
+///         .gen_with(TraversePortalsGenerator::new(RecordingGenerator { visited: &visited }));
+///     assert!(*visited.borrow() == targets.to_vec());
+/// }
+///```
 pub struct TraversePortalsGenerator<TDunGen>
 where
     TDunGen: DoesDunGen,
@@ -84,13 +128,15 @@ where
             let map = &maps[map_id].read();
             for portal in map.portals() {
                 let target_map_id = portal.target();
-                target_map_ids.push(target_map_id);
+                target_map_ids.push((target_map_id, *portal.portal_to_map_facing()));
             }
         }
 
-        for target_map_id in target_map_ids {
+        for (target_map_id, portal_to_map_facing) in target_map_ids {
             self.dun_gen_map(target_map_id);
-            self.dun_gen.dun_gen_map(target_map_id);
+            with_portal_facing(portal_to_map_facing, || {
+                self.dun_gen.dun_gen_map(target_map_id)
+            });
         }
     }
 
@@ -110,13 +156,15 @@ where
             let map = &maps.read();
             for portal in map.portals() {
                 let target_map_id = portal.target();
-                target_map_ids.push(target_map_id);
+                target_map_ids.push((target_map_id, *portal.portal_to_map_facing()));
             }
         }
 
-        for target_map_id in target_map_ids {
+        for (target_map_id, portal_to_map_facing) in target_map_ids {
             self.dun_gen_map(target_map_id);
-            self.dun_gen.dun_gen_map(target_map_id);
+            with_portal_facing(portal_to_map_facing, || {
+                self.dun_gen.dun_gen_map(target_map_id)
+            });
         }
     }
 }
@@ -1,8 +1,6 @@
 // External includes.
 
 // Standard includes.
-use std::collections::HashSet;
-use std::sync::RwLock;
 
 // Internal includes.
 use super::*;
@@ -47,12 +45,54 @@ use super::*;
 /// }
 /// assert!(count == 5);
 ///```
+///
+/// The inner generator is applied exactly once to the current map, and then exactly once to each map its portals lead to, in the order of the portals; the maps reached through those maps' portals are not generated. A map which several portals lead to is only generated once, and a portal back to the current map is skipped. Nothing is remembered between runs, so running the generator again applies the inner generator again.
+///```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// use std::cell::RefCell;
+///
+/// struct RecordingGenerator<'a> {
+///     visited: &'a RefCell<Vec<MapId>>,
+/// }
+///
+/// impl<'a> DoesDunGen for RecordingGenerator<'a> {
+///     fn dun_gen_map(&self, map_id: MapId) {
+///         self.visited.borrow_mut().push(map_id);
+///     }
+/// }
+///
+/// let map_id = SparseMap::new();
+/// let targets = [SparseMap::new(), SparseMap::new()];
+/// let far_map_id = SparseMap::new();
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     map.add_portal(Position::new(0, 1), CardinalDirection::East, Position::zero(), targets[0]);
+///     map.add_portal(Position::new(2, 1), CardinalDirection::West, Position::zero(), targets[1]);
+///     // A second portal to the same map.
+///     map.add_portal(Position::new(1, 0), CardinalDirection::South, Position::zero(), targets[1]);
+///     // A portal back to the first map, and a portal on to a map two portals away.
+///     let mut target_map = maps[targets[0]].write();
+///     target_map.add_portal(Position::new(0, 0), CardinalDirection::East, Position::zero(), map_id);
+///     target_map.add_portal(Position::new(1, 0), CardinalDirection::West, Position::zero(), far_map_id);
+/// }
+///
+/// let visited = RefCell::new(Vec::new());
+/// let traverse = TraverseThisAndPortalsGenerator::new(RecordingGenerator { visited: &visited });
+/// traverse.dun_gen_map(map_id);
+/// assert!(*visited.borrow() == vec![map_id, targets[0], targets[1]]);
+///
+/// traverse.dun_gen_map(map_id);
+/// assert!(*visited.borrow() == vec![map_id, targets[0], targets[1], map_id, targets[0], targets[1]]);
+///```
+///
+/// To apply a generator to every map reachable through portals, however far away, use [`RecursiveTraverseGenerator`](struct.RecursiveTraverseGenerator.html).
 pub struct TraverseThisAndPortalsGenerator<TDunGen>
 where
     TDunGen: DoesDunGen,
 {
     dun_gen: TDunGen,
-    visited_maps: RwLock<HashSet<MapId>>,
 }
 
 impl<TDunGen> TraverseThisAndPortalsGenerator<TDunGen>
@@ -61,68 +101,40 @@ where
 {
     /// Creates a dungeon generator that traverses the current map and portals.
     pub fn new(dun_gen: TDunGen) -> Self {
-        Self {
-            dun_gen,
-            visited_maps: RwLock::new(HashSet::new()),
-        }
+        Self { dun_gen }
     }
-}
-
-impl<TDunGen> DoesDunGen for TraverseThisAndPortalsGenerator<TDunGen>
-where
-    TDunGen: DoesDunGen,
-{
-    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
-        let map_id = target.get_map_id();
-        {
-            let mut visited_maps = self.visited_maps.write().unwrap();
-            if visited_maps.contains(&map_id) {
-                return;
-            }
-
-            visited_maps.insert(map_id);
-        }
 
+    fn dun_gen_portal_targets(&self, map_id: MapId) {
         let mut target_map_ids = Vec::new();
         {
-            self.dun_gen.dun_gen(target);
             let maps = &MAPS.read();
             let map = &maps[map_id].read();
             for portal in map.portals() {
                 let target_map_id = portal.target();
-                target_map_ids.push(target_map_id);
+                if target_map_id != map_id && !target_map_ids.contains(&target_map_id) {
+                    target_map_ids.push(target_map_id);
+                }
             }
         }
 
-        let target_map_ids = target_map_ids;
         for target_map_id in target_map_ids {
-            self.dun_gen_map(target_map_id);
+            self.dun_gen.dun_gen_map(target_map_id);
         }
     }
+}
 
-    fn dun_gen_map(&self, map_id: MapId) {
-        {
-            let mut visited_maps = self.visited_maps.write().unwrap();
-            if visited_maps.contains(&map_id) {
-                return;
-            }
-
-            visited_maps.insert(map_id);
-        }
-
-        let mut target_map_ids = Vec::new();
-        {
-            self.dun_gen.dun_gen_map(map_id);
-            let maps = &MAPS.read();
-            let map = &maps[map_id].read();
-            for portal in map.portals() {
-                let target_map_id = portal.target();
-                target_map_ids.push(target_map_id);
-            }
-        }
+impl<TDunGen> DoesDunGen for TraverseThisAndPortalsGenerator<TDunGen>
+where
+    TDunGen: DoesDunGen,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen.dun_gen(target);
+        self.dun_gen_portal_targets(map_id);
+    }
 
-        for target_map_id in target_map_ids {
-            self.dun_gen_map(target_map_id);
-        }
+    fn dun_gen_map(&self, map_id: MapId) {
+        self.dun_gen.dun_gen_map(map_id);
+        self.dun_gen_portal_targets(map_id);
     }
 }
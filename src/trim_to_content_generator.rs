@@ -0,0 +1,124 @@
+// External includes.
+
+// Standard includes.
+use std::collections::HashMap;
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+use crate::portal_ext::move_portal_label;
+
+/// A generator for shrinking a [`Map`](trait.Map.html) to the smallest [`Area`](geometry/struct.Area.html) enclosing its content.
+///
+/// The `TrimToContentGenerator` moves all tiles and [`Portal`](struct.Portal.html)s so that the top-left of the map's content sits at the local [`Position`](geometry/struct.Position.html) (0, 0), and then sets the map's [`Size`](geometry/struct.Size.html) to the size of the content. Content is any tile which is not [`TileType`](enum.TileType.html)::Void. Maps with no content are left untouched.
+///
+/// The `TrimToContentGenerator` is intended for maps without sub-maps.
+///
+/// Will create a map with a `Size` of 100 tiles wide by 100 tiles high, with a walled oval cave in one corner, and then trim the map down to the cave.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let cave = Oval::new(Position::new(80, 85), Size::new(12, 9));
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(FillTilesGenerator::new(Size::new(100, 100), TileType::Void))
+///     .gen_with(FillTilesGenerator::new(cave, TileType::Floor))
+///     .gen_with(WalledRoomGenerator::new(cave))
+///     .build();
+///
+/// let (content_area, before) = {
+///     let maps = MAPS.read();
+///     let map = maps[map_id].read();
+///     assert!(*map.size() == Size::new(100, 100));
+///     let content_area = map.floor_bounding_box().unwrap();
+///     let mut before = Vec::new();
+///     map.visit_tiles(|position, tile_type| before.push((position, tile_type)));
+///     (content_area, before)
+/// };
+///
+/// DunGen::new(map_id).gen_with(TrimToContentGenerator::new());
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(*map.size() == *content_area.size());
+/// assert!(
+///     map.floor_bounding_box() == Some(Area::new(Position::zero(), *content_area.size())));
+/// for (position, tile_type) in before {
+///     let local_position = position - *content_area.position();
+///     if map.is_local_position_valid(local_position) {
+///         assert!(map.tile_type_at_local(local_position).unwrap_or(TileType::Void) == tile_type);
+///     } else {
+///         assert!(tile_type == TileType::Void);
+///     }
+/// }
+/// ```
+pub struct TrimToContentGenerator {}
+
+impl TrimToContentGenerator {
+    /// Creates a new generator for trimming a map to its content.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl DoesDunGen for TrimToContentGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let content_area = match map.floor_bounding_box() {
+            Some(content_area) => content_area,
+            None => return,
+        };
+        let offset = *content_area.position();
+        if offset == Position::zero() && *content_area.size() == *map.size() {
+            return;
+        }
+
+        let mut old_tiles = HashMap::new();
+        for y in 0..map.size().height() {
+            for x in 0..map.size().width() {
+                let position = Position::new(x as i32, y as i32);
+                if let Some(tile_type) = map.tile_type_at_local(position) {
+                    old_tiles.insert(position, tile_type);
+                }
+            }
+        }
+
+        // Clear out everything that will not be overwritten by the moved content.
+        for position in old_tiles.keys() {
+            if !content_area.size().intersects_local_position(*position)
+                || !old_tiles.contains_key(&(*position + offset))
+            {
+                map.tile_type_at_local_set(*position, TileType::Void);
+            }
+        }
+
+        for (position, tile_type) in old_tiles {
+            let new_position = position - offset;
+            if content_area.size().intersects_local_position(new_position) {
+                map.tile_type_at_local_set(new_position, tile_type);
+            }
+        }
+
+        for portal_mut in map.portals_mut() {
+            let old_position = *portal_mut.local_position();
+            let target = portal_mut.target();
+            *portal_mut.local_position_mut() = old_position - offset;
+            move_portal_label(
+                map_id,
+                (old_position, target),
+                (old_position - offset, target),
+            );
+        }
+
+        map.resize(*content_area.size());
+    }
+}
@@ -0,0 +1,122 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A [`Portal`](struct.Portal.html) with no matching portal leading back from its target map, as reported by [`validate_portal_reciprocity()`](fn.validate_portal_reciprocity.html).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PortalMismatch {
+    map_id: MapId,
+    local_position: Position,
+    target: MapId,
+    portal_to_map_position: Position,
+}
+
+impl PortalMismatch {
+    /// The `MapId` of the map the portal is on.
+    pub fn map_id(&self) -> MapId {
+        self.map_id
+    }
+
+    /// The local position of the portal on its map.
+    pub fn local_position(&self) -> Position {
+        self.local_position
+    }
+
+    /// The `MapId` of the map the portal leads to.
+    pub fn target(&self) -> MapId {
+        self.target
+    }
+
+    /// The local position on the target map the portal leads to, where the matching portal was expected.
+    pub fn portal_to_map_position(&self) -> Position {
+        self.portal_to_map_position
+    }
+}
+
+/// Returns every [`Portal`](struct.Portal.html) reachable from the given map which has no valid reciprocal.
+///
+/// A portal's reciprocal is a portal on its target map, at the position the portal leads to, which leads back to the portal's map and position. Maps are checked in the order returned by [`reachable_maps()`](fn.reachable_maps.html), and each map's portals in the order they were added. Useful for checking hand-authored or merged maps, which [`ReciprocatePortalsGenerator`](struct.ReciprocatePortalsGenerator.html) can then fix.
+///
+/// Will link two rooms with a one-way portal, report it, and then add the missing portal back.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .build();
+/// let target_map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .build();
+/// {
+///     let maps = MAPS.read();
+///     let mut map = maps[map_id].write();
+///     map.add_portal(Position::new(7, 2), CardinalDirection::East, Position::new(0, 3), target_map_id);
+/// }
+///
+/// let mismatches = validate_portal_reciprocity(map_id);
+/// assert!(mismatches.len() == 1);
+/// assert!(mismatches[0].map_id() == map_id);
+/// assert!(mismatches[0].local_position() == Position::new(7, 2));
+/// assert!(mismatches[0].target() == target_map_id);
+/// assert!(mismatches[0].portal_to_map_position() == Position::new(0, 3));
+///
+/// DunGen::new(map_id)
+///     .gen_with(TraverseThisAndPortalsGenerator::new(ReciprocatePortalsGenerator::new()));
+/// assert!(validate_portal_reciprocity(map_id).is_empty());
+/// ```
+pub fn validate_portal_reciprocity(root: MapId) -> Vec<PortalMismatch> {
+    let reachable = reachable_maps(root);
+    let maps = &MAPS.read();
+    let mut output = Vec::new();
+    for map_id in reachable {
+        let map = &maps[map_id].read();
+        for portal in map.portals() {
+            let target = portal.target();
+            let local_position = *portal.local_position();
+            let portal_to_map_position = *portal.portal_to_map_position();
+            let has_reciprocal = if target == map_id {
+                map.portals().into_iter().any(|other_portal| {
+                    is_reciprocal(other_portal, map_id, local_position, portal_to_map_position)
+                })
+            } else {
+                maps[target]
+                    .read()
+                    .portals()
+                    .into_iter()
+                    .any(|other_portal| {
+                        is_reciprocal(other_portal, map_id, local_position, portal_to_map_position)
+                    })
+            };
+
+            if !has_reciprocal {
+                output.push(PortalMismatch {
+                    map_id,
+                    local_position,
+                    target,
+                    portal_to_map_position,
+                });
+            }
+        }
+    }
+
+    output
+}
+
+fn is_reciprocal(
+    other_portal: &Portal,
+    map_id: MapId,
+    local_position: Position,
+    portal_to_map_position: Position,
+) -> bool {
+    other_portal.target() == map_id
+        && *other_portal.local_position() == portal_to_map_position
+        && *other_portal.portal_to_map_position() == local_position
+}
@@ -4,13 +4,14 @@
 
 // Internal includes.
 use super::*;
+use crate::fractional_area::provide_placed_shape_within;
 use crate::geometry::*;
 
 /// A generator for walling in a map.
 ///
 /// The `WalledRoomGenerator` can be called statically to generate [`TileType`](enum.TileType.html)::Wall around the perimeter of the map, or with an explicit size to add internal `TileType::Wall`.
 ///
-/// The walls will be generated as a rectangle defined by an [`Area`](geometry/struct.Area.html) starting from the [0, 0] [`LocalPosition`](geometry/struct.LocalPosition.html).
+/// The walls will be generated as a rectangle defined by an [`Area`](geometry/struct.Area.html) starting from the [0, 0] [`LocalPosition`](geometry/struct.LocalPosition.html). Where positions have been masked out of the map's bounds with [`MapExt::set_in_bounds()`](trait.MapExt.html#method.set_in_bounds), the tiles next to them, including diagonally, are walled as well, so that the walls follow the map's shape.
 ///
 /// Will generate a walled map 8 tiles wide, and 6 tiles high; its internal area will consist of `TileType::Floor` and be 6 tiles wide, and 4 tiles high, with the remainder being walls.
 /// ```
@@ -79,6 +80,7 @@ where
 {
     provides_placed_shape: TProvidesPlacedShape,
     dont_replace: &'a [Option<TileType>],
+    sides: Option<Vec<CardinalDirection>>,
 }
 
 impl<'a, TProvidesPlacedShape> WalledRoomGenerator<'a, TProvidesPlacedShape>
@@ -90,6 +92,7 @@ where
         Self {
             provides_placed_shape,
             dont_replace: &[Some(TileType::Portal)],
+            sides: None,
         }
     }
 
@@ -101,12 +104,76 @@ where
         Self {
             provides_placed_shape,
             dont_replace,
+            sides: None,
         }
     }
 
+    /// Restricts the walls to the given sides of the shape; for example, `CardinalDirection::North` for the top edge. By default, every side is walled.
+    ///
+    /// A tile is on a side if the tile next to it in that direction is outside of the shape, or masked out of the map's bounds with [`MapExt::set_in_bounds()`](trait.MapExt.html#method.set_in_bounds), so a corner is on two sides. Useful when joining maps together, where the shared edge should be left open.
+    /// ```
+    /// # use dungen_minion::geometry::*;
+    /// # use dungen_minion::*;
+    /// let map_id =
+    ///     DunGen::new(SparseMap::new())
+    ///     .gen_with(EmptyRoomGenerator::new(Size::new(8, 6)))
+    ///     .gen_with(
+    ///         WalledRoomGenerator::new(Size::zero())
+    ///             .with_sides(&[CardinalDirection::North]))
+    ///     .build();
+    ///
+    /// let maps = MAPS.read();
+    /// let map = maps[map_id].read();
+    ///
+    /// for x in 0..8 {
+    ///     assert!(map.tile_type_at_local(Position::new(x, 0)) == Some(TileType::Wall));
+    ///     assert!(map.tile_type_at_local(Position::new(x, 5)) == Some(TileType::Floor));
+    /// }
+    /// for y in 1..6 {
+    ///     assert!(map.tile_type_at_local(Position::new(0, y)) == Some(TileType::Floor));
+    ///     assert!(map.tile_type_at_local(Position::new(7, y)) == Some(TileType::Floor));
+    /// }
+    /// assert!(map.count_tiles(TileType::Wall) == 8);
+    /// ```
+    pub fn with_sides(mut self, sides: &[CardinalDirection]) -> Self {
+        self.sides = Some(sides.to_vec());
+        self
+    }
+
     fn dont_replace(&self, check: &Option<TileType>) -> bool {
         self.dont_replace.contains(check)
     }
+
+    fn is_outside(map: &dyn Map, shape: &dyn PlacedShape, position: Position) -> bool {
+        !shape.intersects_position(position) || !map.is_in_bounds(position)
+    }
+
+    fn is_on_edge(map: &dyn Map, shape: &dyn PlacedShape, position: Position) -> bool {
+        // A tile next to a position masked out of the map's bounds is on the edge of the map, as
+        // much as a tile on the edge of the shape is.
+        shape.contains_position(position) == Containment::Intersects
+            || (-1..=1).any(|y| (-1..=1).any(|x| !map.is_in_bounds(position + Position::new(x, y))))
+    }
+
+    fn is_on_allowed_side(
+        &self,
+        map: &dyn Map,
+        shape: &dyn PlacedShape,
+        position: Position,
+    ) -> bool {
+        match &self.sides {
+            Some(sides) => sides.iter().any(|side| {
+                let direction = match side {
+                    CardinalDirection::North => Position::NORTH,
+                    CardinalDirection::East => Position::EAST,
+                    CardinalDirection::South => Position::SOUTH,
+                    CardinalDirection::West => Position::WEST,
+                };
+                Self::is_outside(map, shape, position + direction)
+            }),
+            None => true,
+        }
+    }
 }
 
 impl<'a, TProvidesPlacedShape> DoesDunGen for WalledRoomGenerator<'a, TProvidesPlacedShape>
@@ -122,8 +189,8 @@ where
         let maps = &MAPS.read();
         let map = &mut maps[map_id].write();
 
-        let shape = self.provides_placed_shape.provide_placed_shape();
         let possible_area = Area::from(*map.size());
+        let shape = provide_placed_shape_within(&self.provides_placed_shape, possible_area);
         let shape = if shape.width() > 0 || shape.height() > 0 {
             shape
         } else {
@@ -138,7 +205,10 @@ where
             for x in shape.left()..=shape.right() {
                 let position = Position::new(x, y);
                 if !self.dont_replace(&map.tile_type_at_local(position))
-                    && shape.contains_position(position) == Containment::Intersects
+                    && shape.intersects_position(position)
+                    && map.is_in_bounds(position)
+                    && Self::is_on_edge(map.as_ref(), &*shape, position)
+                    && self.is_on_allowed_side(map.as_ref(), &*shape, position)
                 {
                     map.tile_type_at_local_set(position, TileType::Wall);
                 }
@@ -0,0 +1,62 @@
+// External includes.
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+
+// Standard includes.
+
+// Internal includes.
+use crate::dun_gen_rng::dun_gen_rng;
+use crate::geometry::*;
+
+/// Provides a [`Count`](geometry/type.Count.html) chosen from a set of counts, each with its own weight.
+///
+/// Each count is chosen with a probability of its weight divided by the sum of all the weights; a count with a weight of 0 is never chosen. Useful for biasing, for example, the number of portals toward a typical value while still allowing the occasional outlier.
+///
+/// # Panics
+///
+/// [`WeightedCount::new()`](#method.new) panics if there are no weights, or if every weight is 0.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let weighted_count = WeightedCount::new(vec![(1, 0), (2, 3), (3, 1)]);
+/// let mut frequencies = [0; 4];
+/// for _ in 0..10_000 {
+///     frequencies[weighted_count.provide_count()] += 1;
+/// }
+///
+/// assert!(frequencies[0] == 0);
+/// assert!(frequencies[1] == 0);
+/// // The expected frequencies are 7,500 and 2,500.
+/// assert!(frequencies[2] > 7_000 && frequencies[2] < 8_000);
+/// assert!(frequencies[3] > 2_000 && frequencies[3] < 3_000);
+/// ```
+#[derive(Clone, Debug)]
+pub struct WeightedCount {
+    counts: Vec<Count>,
+    weighted_index: WeightedIndex<u32>,
+}
+
+impl WeightedCount {
+    /// Creates a new `WeightedCount` from a list of counts and their weights.
+    pub fn new(weights: Vec<(Count, u32)>) -> Self {
+        let weighted_index = WeightedIndex::new(weights.iter().map(|(_, weight)| *weight))
+            .expect("WeightedCount requires at least one non-zero weight.");
+
+        Self {
+            counts: weights.into_iter().map(|(count, _)| count).collect(),
+            weighted_index,
+        }
+    }
+}
+
+impl Distribution<Count> for WeightedCount {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Count {
+        self.counts[self.weighted_index.sample(rng)]
+    }
+}
+
+impl ProvidesCount for WeightedCount {
+    fn provide_count(&self) -> Count {
+        self.sample(&mut dun_gen_rng())
+    }
+}
@@ -0,0 +1,106 @@
+// External includes.
+use rand::{Rng, RngCore};
+
+// Standard includes.
+use std::sync::RwLock;
+
+// Internal includes.
+use super::*;
+use crate::dun_gen_rng::dun_gen_rng;
+use crate::geometry::*;
+
+/// A generator for scattering a [`TileType`](enum.TileType.html) over the floor of a map.
+///
+/// The `WeightedTileGenerator` visits every `TileType::Floor` tile within the map's [`Size`](geometry/struct.Size.html), and replaces it with the target `TileType` with the given probability; walls, portals, and every other `TileType` are left as-is. By default, the thread-local random number generator is used; a specific generator can be provided with [`WeightedTileGenerator::with_rng()`](#method.with_rng), for example to reproduce the same scattering.
+///
+/// Will create several walled maps with portals, and scatter walls over their floors; with a probability of 1.0 every floor tile is replaced, and with a probability of 0.0 none are.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// for (i, size) in [Size::new(8, 6), Size::new(12, 8), Size::new(5, 20)].iter().enumerate() {
+///     let generate = |probability: f64| {
+///         DunGen::new(SparseMap::new())
+///             .gen_with(EmptyRoomGenerator::new(*size))
+///             .gen_with(WalledRoomGenerator::new(Size::zero()))
+///             .gen_with(EdgePortalsGenerator::new(2, Box::new(|| SparseMap::new())))
+///             .gen_with(
+///                 WeightedTileGenerator::new(TileType::Wall, probability)
+///                     .with_rng(StdRng::seed_from_u64(i as u64)),
+///             )
+///             .build()
+///     };
+///     let map_count = |map_id: MapId, tile_type: TileType| {
+///         MAPS.read()[map_id].read().count_tiles(tile_type)
+///     };
+///     let area = (size.width() * size.height()) as usize;
+///
+///     let map_id = generate(1.0);
+///     assert!(map_count(map_id, TileType::Floor) == 0);
+///     assert!(map_count(map_id, TileType::Portal) == 2);
+///     assert!(map_count(map_id, TileType::Wall) == area - 2);
+///
+///     let map_id = generate(0.0);
+///     let inner_area = ((size.width() - 2) * (size.height() - 2)) as usize;
+///     assert!(map_count(map_id, TileType::Floor) == inner_area);
+///     assert!(map_count(map_id, TileType::Portal) == 2);
+/// }
+/// ```
+pub struct WeightedTileGenerator {
+    target: TileType,
+    probability: f64,
+    rng: Option<RwLock<Box<dyn RngCore + Send + Sync>>>,
+}
+
+impl WeightedTileGenerator {
+    /// Creates a new generator for replacing floor tiles with the target `TileType`, each with the given probability.
+    pub fn new(target: TileType, probability: f64) -> Self {
+        Self {
+            target,
+            probability,
+            rng: None,
+        }
+    }
+
+    /// Sets the random number generator used to decide which floor tiles are replaced.
+    pub fn with_rng<TRng>(mut self, rng: TRng) -> Self
+    where
+        TRng: 'static + RngCore + Send + Sync,
+    {
+        self.rng = Some(RwLock::new(Box::new(rng)));
+        self
+    }
+}
+
+impl DoesDunGen for WeightedTileGenerator {
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+
+        let probability = self.probability.clamp(0.0, 1.0);
+        let mut default_rng = dun_gen_rng();
+        let mut rng_lock = self.rng.as_ref().map(|rng| rng.write().unwrap());
+        let rng: &mut dyn RngCore = match rng_lock.as_mut() {
+            Some(rng) => &mut ***rng,
+            None => &mut default_rng,
+        };
+
+        for y in 0..map.size().height() {
+            for x in 0..map.size().width() {
+                let position = Position::new(x as i32, y as i32);
+                if map.tile_type_at_local(position) == Some(TileType::Floor)
+                    && rng.gen_bool(probability)
+                {
+                    map.tile_type_at_local_set(position, self.target);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+// External includes.
+
+// Standard includes.
+
+// Internal includes.
+use super::*;
+use crate::geometry::*;
+
+/// A generator for linking every edge tile of a map to the neighbouring map in a world of map cells, so that walking off one map leads onto the next.
+///
+/// The `WrapEdgePortalsGenerator` adds a [`Portal`](struct.Portal.html), and a [`TileType`](enum.TileType.html)::Portal, to every tile on the edges of the map, excluding the corners. For each tile, the given function is called with the direction of the neighbouring map, `CardinalDirection::West` for the left edge and so on, and the tile's local [`Position`](geometry/struct.Position.html); it returns the `MapId` of the neighbouring map. As with [`EdgePortalsGenerator`](struct.EdgePortalsGenerator.html), each portal faces from its edge into the map; so a portal on the left edge faces `CardinalDirection::East`. Each portal leads to the matching tile on the opposite edge of the neighbouring map, which is assumed to have the same [`Size`](geometry/struct.Size.html). Maps less than 3 tiles wide or 3 tiles high have no edge tiles which are not corners, and are left as-is.
+///
+/// Portals are added to the north edge, then the east, south, and west edges; along each edge, in order of increasing position.
+///
+/// Will create a map 6 tiles wide by 5 tiles high, with a neighbouring map on each side, and link every edge tile to the neighbours.
+/// ```
+/// # use dungen_minion::geometry::*;
+/// # use dungen_minion::*;
+/// let neighbours = [
+///     (CardinalDirection::North, SparseMap::new()),
+///     (CardinalDirection::East, SparseMap::new()),
+///     (CardinalDirection::South, SparseMap::new()),
+///     (CardinalDirection::West, SparseMap::new()),
+/// ];
+/// let neighbour = move |direction: CardinalDirection| {
+///     neighbours.iter().find(|(side, _)| *side == direction).unwrap().1
+/// };
+/// let map_id =
+///     DunGen::new(SparseMap::new())
+///     .gen_with(EmptyRoomGenerator::new(Size::new(6, 5)))
+///     .gen_with(WalledRoomGenerator::new(Size::zero()))
+///     .gen_with(WrapEdgePortalsGenerator::new(move |direction, _position| neighbour(direction)))
+///     .build();
+///
+/// let maps = MAPS.read();
+/// let map = maps[map_id].read();
+///
+/// assert!(map.portal_count() == (4 * 2) + (3 * 2));
+/// for y in 0..5 {
+///     for x in 0..6 {
+///         let position = Position::new(x, y);
+///         let direction = match (x, y) {
+///             (0, 0) | (5, 0) | (0, 4) | (5, 4) => None,
+///             (_, 0) => Some(CardinalDirection::North),
+///             (5, _) => Some(CardinalDirection::East),
+///             (_, 4) => Some(CardinalDirection::South),
+///             (0, _) => Some(CardinalDirection::West),
+///             _ => None,
+///         };
+///         match direction {
+///             Some(direction) => {
+///                 assert!(map.tile_type_at_local(position) == Some(TileType::Portal));
+///                 let portal = map.portal_at(position).unwrap();
+///                 assert!(*portal.portal_to_map_facing() == -direction);
+///                 assert!(portal.target() == neighbour(direction));
+///             }
+///             None => assert!(map.portal_at(position).is_none()),
+///         }
+///     }
+/// }
+///
+/// // A portal on the left edge leads to the right edge of the map to the west.
+/// let portal = map.portal_at(Position::new(0, 2)).unwrap();
+/// assert!(*portal.portal_to_map_position() == Position::new(5, 2));
+/// ```
+pub struct WrapEdgePortalsGenerator<TFunc>
+where
+    TFunc: Fn(CardinalDirection, Position) -> MapId,
+{
+    neighbour_func: TFunc,
+}
+
+impl<TFunc> WrapEdgePortalsGenerator<TFunc>
+where
+    TFunc: Fn(CardinalDirection, Position) -> MapId,
+{
+    /// Creates a new generator for linking the edges of a map to its neighbours, which are provided by the given function.
+    pub fn new(neighbour_func: TFunc) -> Self {
+        Self { neighbour_func }
+    }
+}
+
+impl<TFunc> DoesDunGen for WrapEdgePortalsGenerator<TFunc>
+where
+    TFunc: Fn(CardinalDirection, Position) -> MapId,
+{
+    fn dun_gen(&self, target: &mut dyn SupportsDunGen) {
+        let map_id = target.get_map_id();
+        self.dun_gen_map(map_id);
+    }
+
+    fn dun_gen_map(&self, map_id: MapId) {
+        let size = *MAPS.read()[map_id].read().size();
+        if size.width() < 3 || size.height() < 3 {
+            return;
+        }
+
+        let (right, bottom) = (size.width() as i32 - 1, size.height() as i32 - 1);
+        let mut edge_tiles = Vec::new();
+        for x in 1..right {
+            edge_tiles.push((
+                CardinalDirection::North,
+                Position::new(x, 0),
+                Position::new(x, bottom),
+            ));
+        }
+        for y in 1..bottom {
+            edge_tiles.push((
+                CardinalDirection::East,
+                Position::new(right, y),
+                Position::new(0, y),
+            ));
+        }
+        for x in 1..right {
+            edge_tiles.push((
+                CardinalDirection::South,
+                Position::new(x, bottom),
+                Position::new(x, 0),
+            ));
+        }
+        for y in 1..bottom {
+            edge_tiles.push((
+                CardinalDirection::West,
+                Position::new(0, y),
+                Position::new(right, y),
+            ));
+        }
+
+        // The neighbouring maps are found before locking the map, as the function may create them.
+        let portals = edge_tiles
+            .into_iter()
+            .map(|(direction, local_position, portal_to_map_position)| {
+                (
+                    direction,
+                    local_position,
+                    portal_to_map_position,
+                    (self.neighbour_func)(direction, local_position),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let maps = &MAPS.read();
+        let map = &mut maps[map_id].write();
+        for (direction, local_position, portal_to_map_position, target) in portals {
+            map.add_portal(local_position, -direction, portal_to_map_position, target);
+        }
+    }
+}